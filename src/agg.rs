@@ -0,0 +1,387 @@
+use alloc::vec::Vec;
+use core::{fmt, iter::Rev, marker::PhantomData, slice};
+
+use crate::{
+    prefix::{PrefixStack, Step, Summary},
+    LIFOEntry, Stack,
+};
+
+/// An associative operation with an identity element, used by [`AggStack`] to aggregate
+/// its items.
+///
+/// Every item is lifted into the aggregate type [`Monoid::Agg`] with [`Monoid::lift`],
+/// and the lifted values are combined. The aggregate type can differ from the item type,
+/// for example, the lengths of [`String`](alloc::string::String) items can be summed
+/// into a `usize`.
+///
+/// ## Notes
+///
+/// `combine` must be associative, and `identity` must be its identity element.
+/// The operation doesn't have to be commutative: the aggregate combines the items
+/// from the bottom to the top of the stack.
+pub trait Monoid<T> {
+    /// The type of the aggregates.
+    type Agg;
+
+    /// Returns the identity element, which is the aggregate of an empty stack.
+    fn identity() -> Self::Agg;
+
+    /// Returns the aggregate of the single item.
+    fn lift(item: &T) -> Self::Agg;
+
+    /// Combines two aggregates, where `a` is closer to the bottom of the stack than `b`.
+    fn combine(a: &Self::Agg, b: &Self::Agg) -> Self::Agg;
+}
+
+/// The monoid `M` with the operands of [`Monoid::combine`] swapped.
+///
+/// An [`AggStack`] with this monoid aggregates its items from the top to the bottom,
+/// which is the order of the front half of a [`TwoStackQueue`](crate::TwoStackQueue).
+pub struct Dual<M>(PhantomData<fn() -> M>);
+
+impl<T, M: Monoid<T>> Monoid<T> for Dual<M> {
+    type Agg = M::Agg;
+
+    #[inline]
+    fn identity() -> Self::Agg {
+        M::identity()
+    }
+
+    #[inline]
+    fn lift(item: &T) -> Self::Agg {
+        M::lift(item)
+    }
+
+    #[inline]
+    fn combine(a: &Self::Agg, b: &Self::Agg) -> Self::Agg {
+        M::combine(b, a)
+    }
+}
+
+/// A stack that aggregates its items with the [`Monoid`] `M`, so that
+/// [`AggStack::aggregate`] is O(1).
+///
+/// The aggregate of every prefix of the stack is stored alongside the items, so popping
+/// restores the aggregate of the remaining items without recombining them.
+///
+/// ## Notes
+///
+/// Mutable references to the items invalidate the aggregates, which are then recombined
+/// the same way as the extrema of [`MinMaxStack`](crate::MinMaxStack), so the stack
+/// is not [`Sync`] either.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{AggStack, Monoid, Stack};
+///
+/// struct Sum;
+///
+/// impl Monoid<u32> for Sum {
+///     type Agg = u32;
+///
+///     fn identity() -> u32 {
+///         0
+///     }
+///
+///     fn lift(item: &u32) -> u32 {
+///         *item
+///     }
+///
+///     fn combine(a: &u32, b: &u32) -> u32 {
+///         a + b
+///     }
+/// }
+///
+/// let mut stack = AggStack::<u32, Sum>::new();
+/// stack.s_extend([1, 2, 3]);
+/// assert_eq!(stack.aggregate(), 6);
+///
+/// *stack.lifo().unwrap() = 10;
+/// assert_eq!(stack.aggregate(), 13);
+///
+/// stack.s_pop();
+/// assert_eq!(stack.aggregate(), 3);
+/// ```
+pub struct AggStack<T, M: Monoid<T>>(PrefixStack<T, Combined<T, M>>);
+
+// The aggregate of a prefix.
+struct Combined<T, M>(PhantomData<fn(T) -> M>);
+
+impl<T, M: Monoid<T>> Summary for Combined<T, M> {
+    type Entry = M::Agg;
+}
+
+impl<T, M: Monoid<T>> Step<T> for Combined<T, M> {
+    fn step(below: Option<&M::Agg>, items: &[T], index: usize) -> M::Agg {
+        let item = M::lift(&items[index]);
+        match below {
+            Some(below) => M::combine(below, &item),
+            None => item,
+        }
+    }
+}
+
+impl<T, M: Monoid<T>> AggStack<T, M> {
+    /// Creates a new empty stack.
+    pub const fn new() -> Self {
+        Self(PrefixStack::new())
+    }
+
+    /// Returns the number of items in the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.as_slice().len()
+    }
+
+    /// Returns `true` if the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.as_slice().is_empty()
+    }
+
+    /// Returns the items of the stack, from the bottom to the top.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.0.as_slice()
+    }
+
+    /// Returns the items of the stack, from the bottom to the top.
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.0.into_vec()
+    }
+
+    /// Returns the aggregate of the items, from the bottom to the top of the stack,
+    /// or the identity element if the stack is empty.
+    pub fn aggregate(&self) -> M::Agg
+    where
+        M::Agg: Clone,
+    {
+        self.0.summary().unwrap_or_else(M::identity)
+    }
+}
+
+impl<T, M: Monoid<T>> Default for AggStack<T, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, M: Monoid<T>> Clone for AggStack<T, M>
+where
+    M::Agg: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: fmt::Debug, M: Monoid<T>> fmt::Debug for AggStack<T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq, M: Monoid<T>> PartialEq for AggStack<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, M: Monoid<T>> Eq for AggStack<T, M> {}
+
+/// Collects the items from the bottom to the top of the stack.
+impl<T, M: Monoid<T>> FromIterator<T> for AggStack<T, M> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::new();
+        stack.s_extend(iter);
+        stack
+    }
+}
+
+impl<T, M: Monoid<T>> Stack for AggStack<T, M> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, T>>
+    where
+        Self: 'a;
+
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, T>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.0.s_is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.0.s_len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.0.s_push(item);
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // SAFETY: We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.0.s_pop()
+    }
+
+    #[inline]
+    fn s_extend<I: IntoIterator<Item = Self::Item>>(&mut self, iter: I) {
+        self.0.s_extend(iter);
+    }
+
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        self.0.s_truncate(len);
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.0.lifo_ref()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.0.lifo_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.0.peek_nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.0.peek_nth_mut(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.s_remove_nth(n)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.0.iter_lifo()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.0.iter_lifo_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::String, vec};
+
+    struct Gcd;
+
+    impl Monoid<u64> for Gcd {
+        type Agg = u64;
+
+        fn identity() -> u64 {
+            0
+        }
+
+        fn lift(item: &u64) -> u64 {
+            *item
+        }
+
+        fn combine(a: &u64, b: &u64) -> u64 {
+            let (mut a, mut b) = (*a, *b);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        }
+    }
+
+    struct Concat;
+
+    impl Monoid<String> for Concat {
+        type Agg = String;
+
+        fn identity() -> String {
+            String::new()
+        }
+
+        fn lift(item: &String) -> String {
+            item.clone()
+        }
+
+        fn combine(a: &String, b: &String) -> String {
+            a.clone() + b
+        }
+    }
+
+    struct TotalLen;
+
+    impl Monoid<String> for TotalLen {
+        type Agg = usize;
+
+        fn identity() -> usize {
+            0
+        }
+
+        fn lift(item: &String) -> usize {
+            item.len()
+        }
+
+        fn combine(a: &usize, b: &usize) -> usize {
+            a + b
+        }
+    }
+
+    #[test]
+    fn aggregates_follow_mutations() {
+        let mut stack: AggStack<u64, Gcd> = [12, 18, 30].into_iter().collect();
+        assert_eq!(stack.aggregate(), 6);
+        *stack.lifo().unwrap() = 4;
+        assert_eq!(stack.aggregate(), 2);
+        *stack.peek_nth_mut(2).unwrap() = 24;
+        assert_eq!(stack.aggregate(), 2);
+        stack.iter_lifo_mut().for_each(|item| *item *= 3);
+        assert_eq!(stack.aggregate(), 6);
+        assert_eq!(stack.s_remove_nth(0), Some(12));
+        assert_eq!(stack.aggregate(), 18);
+        stack.s_extend([15, 35]);
+        stack.iter_lifo_mut().for_each(|item| *item *= 2);
+        assert_eq!(stack.s_pop(), Some(70));
+        assert_eq!(stack.aggregate(), 6);
+        stack.s_truncate(0);
+        assert_eq!(stack.aggregate(), 0);
+
+        let mut stack = AggStack::<String, Concat>::new();
+        for word in ["a", "b", "c"] {
+            stack.lifo_push(String::from(word)).push('!');
+        }
+        assert_eq!(stack.aggregate(), "a!b!c!");
+        stack.s_pop();
+        assert_eq!(stack.aggregate(), "a!b!");
+        assert_eq!(stack.into_vec(), vec!["a!", "b!"]);
+
+        let mut stack: AggStack<String, TotalLen> =
+            ["ab", "c"].map(String::from).into_iter().collect();
+        stack.lifo().unwrap().push_str("de");
+        assert_eq!(stack.aggregate(), 5);
+        stack.s_pop();
+        assert_eq!(stack.aggregate(), 2);
+    }
+}
@@ -0,0 +1,246 @@
+use core::{iter::Rev, mem::MaybeUninit, slice};
+
+use crate::{index_from_depth, BoundedStack, CapacityError, ContiguousStack, LIFOEntry, Stack};
+
+/// A fixed-capacity stack that stores up to `N` items inline.
+///
+/// Unlike `Vec`, it never allocates, so it is available without the `alloc`
+/// feature.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{ArrayStack, Stack};
+///
+/// let mut stack: ArrayStack<i32, 2> = ArrayStack::new();
+/// assert_eq!(stack.s_push_checked(1), Some(()));
+/// assert_eq!(*stack.lifo_push(2), 2);
+/// // The stack is full, so the item is handed back.
+/// assert_eq!(stack.try_lifo_push(3).err().unwrap().into_inner(), 3);
+/// assert_eq!(stack.as_slice(), &[1, 2]);
+/// ```
+pub struct ArrayStack<T, const N: usize> {
+    len: usize,
+    items: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArrayStack<T, N> {
+    /// Creates a new empty stack.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            items: [const { MaybeUninit::uninit() }; N],
+        }
+    }
+
+    /// Returns the number of items in the stack.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the stack is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of items the stack can hold.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the items of the stack as a slice, from the bottom to the top.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: The first `len` items are initialized.
+        unsafe { slice::from_raw_parts(self.items.as_ptr().cast::<T>(), self.len) }
+    }
+
+    /// Returns the items of the stack as a mutable slice, from the bottom to the top.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: The first `len` items are initialized.
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr().cast::<T>(), self.len) }
+    }
+}
+
+impl<T, const N: usize> Default for ArrayStack<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayStack<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: The first `len` items are initialized and are never used again.
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for ArrayStack<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Stack for ArrayStack<T, N> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, T>>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, T>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        if self.s_try_push(item).is_err() {
+            panic!("ArrayStack is full (capacity is {N})");
+        }
+    }
+
+    #[inline]
+    fn s_try_push(&mut self, item: Self::Item) -> Result<(), CapacityError<Self::Item>> {
+        let Some(slot) = self.items.get_mut(self.len) else {
+            return Err(CapacityError::new(item));
+        };
+        slot.write(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn lifo_push_with<F: FnOnce() -> Self::Item>(&mut self, f: F) -> LIFOEntry<'_, Self> {
+        self.lifo_push_uninit().write(f())
+    }
+
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail: *mut [T] = &mut self.as_mut_slice()[len..];
+        // The length is updated first, so that a panicking destructor can't cause a double drop.
+        self.len = len;
+        // SAFETY: The items in the tail are initialized and are no longer considered live.
+        unsafe { core::ptr::drop_in_place(tail) }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: The item at `len` was initialized and is no longer considered live.
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.as_slice().last()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.as_mut_slice().last_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.as_slice().iter().rev().nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.as_mut_slice().iter_mut().rev().nth(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = index_from_depth(self.len(), n)?;
+        self.as_mut_slice()[index..].rotate_left(1);
+        self.s_pop()
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.as_slice().iter().rev()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.as_mut_slice().iter_mut().rev()
+    }
+}
+
+impl<T, const N: usize> BoundedStack for ArrayStack<T, N> {
+    #[inline]
+    fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+}
+
+unsafe impl<T, const N: usize> ContiguousStack for ArrayStack<T, N> {
+    #[inline]
+    fn spare_top(&mut self) -> Option<&mut MaybeUninit<Self::Item>> {
+        self.items.get_mut(self.len)
+    }
+
+    #[inline]
+    unsafe fn assume_pushed(&mut self) {
+        self.len += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn drops_live_items() {
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut stack: ArrayStack<Counted<'_>, 3> = ArrayStack::new();
+        for _ in 0..3 {
+            stack.s_push(Counted(&drops));
+        }
+        assert!(stack.s_push_checked(Counted(&drops)).is_none());
+        assert_eq!(drops.get(), 1);
+        stack.lifo().unwrap().pop_pointee();
+        assert_eq!(drops.get(), 2);
+        drop(stack);
+        assert_eq!(drops.get(), 4);
+    }
+}
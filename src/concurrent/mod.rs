@@ -0,0 +1,32 @@
+//! Stacks that can be shared between threads.
+
+#[cfg(feature = "concurrent")]
+mod treiber;
+
+#[cfg(feature = "concurrent")]
+pub use treiber::TreiberStack;
+
+/// Implementors of this trait can be used as a [stack] shared between threads.
+///
+/// Unlike [`Stack`](crate::Stack), the operations take `&self`, so the stack can be pushed
+/// to and popped from concurrently, for example, through an `Arc`.
+///
+/// [stack]: https://www.geeksforgeeks.org/stack-data-structure/
+pub trait ConcurrentStack {
+    /// The type of the items stored in the stack.
+    type Item;
+
+    /// Pushes an item to the stack.
+    fn push(&self, item: Self::Item);
+
+    /// Pops an item from the stack.
+    fn pop(&self) -> Option<Self::Item>;
+
+    /// Returns `true` if the stack is empty.
+    ///
+    /// ## Notes
+    ///
+    /// Other threads may push to or pop from the stack right after the check,
+    /// so the result is only a snapshot.
+    fn is_empty(&self) -> bool;
+}
@@ -0,0 +1,333 @@
+use core::{
+    cell::Cell,
+    hint::spin_loop,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::Ordering::{Acquire, Relaxed, Release},
+};
+
+use alloc::{boxed::Box, vec::Vec};
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+
+use super::ConcurrentStack;
+
+/// The number of spins a pusher waits in the elimination slot for a popper.
+const ELIMINATION_SPINS: u32 = 64;
+/// The upper bound of the exponential backoff between the attempts, in spins.
+const MAX_BACKOFF_SPINS: u32 = 1 << 10;
+
+struct Node<T> {
+    // The item is moved out by the thread that unlinks the node,
+    // so the deferred destruction of the node must not drop it.
+    item: ManuallyDrop<T>,
+    next: Atomic<Node<T>>,
+}
+
+/// A lock-free [Treiber stack] with epoch-based memory reclamation.
+///
+/// Optionally, it uses an [elimination backoff] array, where a push and a pop that fail
+/// to update the top of the stack due to contention can exchange the item directly.
+///
+/// [Treiber stack]: https://en.wikipedia.org/wiki/Treiber_stack
+/// [elimination backoff]: https://people.csail.mit.edu/shanir/publications/Lock_Free.pdf
+///
+/// ## Example
+///
+/// ```
+/// use std::{sync::Arc, thread};
+/// use stack_trait::{ConcurrentStack, TreiberStack};
+///
+/// let stack = Arc::new(TreiberStack::with_elimination(4));
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///         let stack = Arc::clone(&stack);
+///         thread::spawn(move || stack.push(i))
+///     })
+///     .collect();
+/// handles.into_iter().for_each(|handle| handle.join().unwrap());
+/// let mut items: Vec<i32> = std::iter::from_fn(|| stack.pop()).collect();
+/// items.sort();
+/// assert_eq!(items, vec![0, 1, 2, 3]);
+/// ```
+pub struct TreiberStack<T> {
+    head: Atomic<Node<T>>,
+    elimination: Box<[Atomic<Node<T>>]>,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: The items are moved between threads but never shared, so `T: Send` is enough.
+unsafe impl<T: Send> Send for TreiberStack<T> {}
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T> TreiberStack<T> {
+    /// Creates a new empty stack without the elimination array.
+    pub fn new() -> Self {
+        Self::with_elimination(0)
+    }
+
+    /// Creates a new empty stack with the elimination array of the given number of slots.
+    ///
+    /// The elimination array reduces the contention on the top of the stack when
+    /// many threads push and pop concurrently. With `0` slots, it is disabled.
+    pub fn with_elimination(slots: usize) -> Self {
+        Self {
+            head: Atomic::null(),
+            elimination: (0..slots)
+                .map(|_| Atomic::null())
+                .collect::<Vec<_>>()
+                .into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pushes an item to the stack.
+    pub fn push(&self, item: T) {
+        let guard = &epoch::pin();
+        let mut node = Owned::new(Node {
+            item: ManuallyDrop::new(item),
+            next: Atomic::null(),
+        });
+        let mut backoff = 1;
+        loop {
+            let head = self.head.load(Acquire, guard);
+            node.next.store(head, Relaxed);
+            match self
+                .head
+                .compare_exchange(head, node, Release, Relaxed, guard)
+            {
+                Ok(_) => return,
+                Err(err) => node = err.new,
+            }
+            node = match self.eliminate_push(node, guard) {
+                Ok(()) => return,
+                Err(node) => node,
+            };
+            backoff = spin(backoff);
+        }
+    }
+
+    /// Pops an item from the stack.
+    pub fn pop(&self) -> Option<T> {
+        let guard = &epoch::pin();
+        let mut backoff = 1;
+        loop {
+            let head = self.head.load(Acquire, guard);
+            // SAFETY: The node can't be destroyed while the thread is pinned.
+            let node = unsafe { head.as_ref() }?;
+            let next = node.next.load(Relaxed, guard);
+            if self
+                .head
+                .compare_exchange(head, next, Relaxed, Relaxed, guard)
+                .is_ok()
+            {
+                // SAFETY: The node was unlinked by this thread, so the item is moved out
+                // exactly once and the node is destroyed exactly once.
+                unsafe { return Some(take_item(head, guard)) }
+            }
+            if let Some(item) = self.eliminate_pop(guard) {
+                return Some(item);
+            }
+            backoff = spin(backoff);
+        }
+    }
+
+    /// Returns `true` if the stack is empty.
+    pub fn is_empty(&self) -> bool {
+        let guard = &epoch::pin();
+        self.head.load(Acquire, guard).is_null()
+    }
+
+    /// Offers the node in a random elimination slot to a concurrent pop.
+    ///
+    /// Returns the node back if no pop took it.
+    fn eliminate_push(&self, node: Owned<Node<T>>, guard: &Guard) -> Result<(), Owned<Node<T>>> {
+        let Some(slot) = self.random_slot() else {
+            return Err(node);
+        };
+        let node = node.into_shared(guard);
+        if slot
+            .compare_exchange(Shared::null(), node, Release, Relaxed, guard)
+            .is_err()
+        {
+            // SAFETY: The node wasn't published, so this thread still owns it.
+            return Err(unsafe { node.into_owned() });
+        }
+        for _ in 0..ELIMINATION_SPINS {
+            if slot.load(Relaxed, guard) != node {
+                break;
+            }
+            spin_loop();
+        }
+        // A pop that took the node defers its destruction, and the thread is pinned,
+        // so no other node can reuse its address and make the withdrawal succeed spuriously.
+        match slot.compare_exchange(node, Shared::null(), Relaxed, Relaxed, guard) {
+            // SAFETY: The node was withdrawn, so this thread owns it again.
+            Ok(_) => Err(unsafe { node.into_owned() }),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Takes the node offered by a concurrent push from a random elimination slot.
+    fn eliminate_pop(&self, guard: &Guard) -> Option<T> {
+        let slot = self.random_slot()?;
+        let node = slot.load(Acquire, guard);
+        if node.is_null() {
+            return None;
+        }
+        slot.compare_exchange(node, Shared::null(), Acquire, Relaxed, guard)
+            .ok()?;
+        // SAFETY: The node was taken from the slot by this thread, so the item is moved out
+        // exactly once and the node is destroyed exactly once.
+        Some(unsafe { take_item(node, guard) })
+    }
+
+    fn random_slot(&self) -> Option<&Atomic<Node<T>>> {
+        if self.elimination.is_empty() {
+            return None;
+        }
+        Some(&self.elimination[random() as usize % self.elimination.len()])
+    }
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        // SAFETY: The stack is exclusively borrowed, so no other thread can access the nodes,
+        // and every push has either linked its node or taken it back from the elimination array.
+        unsafe {
+            let guard = epoch::unprotected();
+            let mut head = self.head.load(Relaxed, guard);
+            while let Some(node) = head.as_ref() {
+                let next = node.next.load(Relaxed, guard);
+                let mut node = head.into_owned();
+                ManuallyDrop::drop(&mut node.item);
+                head = next;
+            }
+        }
+    }
+}
+
+impl<T> ConcurrentStack for TreiberStack<T> {
+    type Item = T;
+
+    #[inline]
+    fn push(&self, item: Self::Item) {
+        TreiberStack::push(self, item);
+    }
+
+    #[inline]
+    fn pop(&self) -> Option<Self::Item> {
+        TreiberStack::pop(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        TreiberStack::is_empty(self)
+    }
+}
+
+/// Moves the item out of the node and defers the destruction of the node.
+///
+/// ## Safety
+///
+/// The node must be unreachable for other threads that are not pinned yet,
+/// and this function must be called only once per node.
+unsafe fn take_item<T>(node: Shared<'_, Node<T>>, guard: &Guard) -> T {
+    let item = ManuallyDrop::into_inner(ptr::read(&node.deref().item));
+    guard.defer_destroy(node);
+    item
+}
+
+/// Spins for the given number of iterations and returns the next, doubled, number.
+fn spin(backoff: u32) -> u32 {
+    for _ in 0..backoff {
+        spin_loop();
+    }
+    (backoff * 2).min(MAX_BACKOFF_SPINS)
+}
+
+/// Returns a thread-local pseudo-random number for picking the elimination slot.
+fn random() -> u32 {
+    std::thread_local! {
+        static STATE: Cell<u32> = const { Cell::new(0) };
+    }
+    STATE.with(|state| {
+        let mut x = state.get();
+        if x == 0 {
+            // Seed with the address of the thread-local, which differs between threads.
+            x = (state as *const Cell<u32> as usize as u32) | 1;
+        }
+        // xorshift32
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        state.set(x);
+        x
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, vec::Vec};
+
+    use super::TreiberStack;
+
+    fn stress(stack: TreiberStack<usize>) {
+        const THREADS: usize = 8;
+        const ITEMS: usize = 10_000;
+
+        let stack = Arc::new(stack);
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+                    for i in 0..ITEMS {
+                        stack.push(t * ITEMS + i);
+                        if i % 2 == 0 {
+                            popped.extend(stack.pop());
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+        let mut items: Vec<usize> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        while let Some(item) = stack.pop() {
+            items.push(item);
+        }
+        items.sort_unstable();
+        assert_eq!(items, (0..THREADS * ITEMS).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stress_plain() {
+        stress(TreiberStack::new());
+    }
+
+    #[test]
+    fn stress_elimination() {
+        stress(TreiberStack::with_elimination(4));
+    }
+
+    #[test]
+    fn drops_remaining_items() {
+        let item = Arc::new(());
+        let stack = TreiberStack::new();
+        for _ in 0..3 {
+            stack.push(Arc::clone(&item));
+        }
+        drop(stack.pop());
+        drop(stack);
+        assert_eq!(Arc::strong_count(&item), 1);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::Stack;
+
+/// An "entry" object corresponding to the element at a given depth of the stack,
+/// where the top element is at depth `0`.
+///
+/// Existence of this object guarantees that the stack is deeper than the depth.
+///
+/// ## Also see
+///
+/// * [`LIFOEntry`](crate::LIFOEntry)
+pub struct DepthEntry<'a, C: ?Sized> {
+    stack: &'a mut C,
+    depth: usize,
+}
+
+impl<'a, C: ?Sized + Stack> DepthEntry<'a, C> {
+    /// Creates a new "entry" object from the mutable reference to the container and the depth.
+    ///
+    /// ## Safety
+    ///
+    /// `depth` must be less than the number of items in the stack.
+    pub unsafe fn new(stack: &'a mut C, depth: usize) -> Self {
+        Self { stack, depth }
+    }
+
+    /// Returns the depth of the element, where the top element is at depth `0`.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Removes the element from the stack.
+    ///
+    /// The elements above it are shifted down by one.
+    pub fn remove_pointee(self) -> C::Item {
+        let DepthEntry { stack, depth } = self;
+        // SAFETY: The stack is deeper than the depth by the virtue of
+        // existence of the DepthEntry object, so the call is safe.
+        unsafe { stack.s_remove_nth_unchecked(depth) }
+    }
+
+    /// Converts the entry into a shared reference to the element with the lifetime
+    /// of the entry.
+    pub fn into_ref(self) -> &'a C::Item {
+        let DepthEntry { stack, depth } = self;
+        // SAFETY: The stack is deeper than the depth by the virtue of
+        // existence of the DepthEntry object, so the call is safe.
+        unsafe { stack.peek_nth_unchecked(depth) }
+    }
+
+    /// Converts the entry into a mutable reference to the element with the lifetime
+    /// of the entry.
+    pub fn into_mut(self) -> &'a mut C::Item {
+        let DepthEntry { stack, depth } = self;
+        // SAFETY: The stack is deeper than the depth by the virtue of
+        // existence of the DepthEntry object, so the call is safe.
+        unsafe { stack.peek_nth_mut_unchecked(depth) }
+    }
+
+    /// Converts the entry into the mutable reference to the stack.
+    pub fn into_stack(self) -> &'a mut C {
+        let DepthEntry { stack, .. } = self;
+        stack
+    }
+}
+
+impl<'a, C: ?Sized + Stack> core::ops::Deref for DepthEntry<'a, C> {
+    type Target = C::Item;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The stack is deeper than the depth, so the call is safe.
+        unsafe { self.stack.peek_nth_unchecked(self.depth) }
+    }
+}
+
+impl<'a, C: ?Sized + Stack> core::ops::DerefMut for DepthEntry<'a, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The stack is deeper than the depth, so the call is safe.
+        unsafe { self.stack.peek_nth_mut_unchecked(self.depth) }
+    }
+}
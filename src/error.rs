@@ -0,0 +1,77 @@
+use core::fmt;
+
+/// An error returned when an item is pushed to a full stack.
+///
+/// The rejected item is handed back and can be recovered with [`CapacityError::into_inner`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapacityError<T>(T);
+
+impl<T> CapacityError<T> {
+    /// Creates a new error holding the rejected item.
+    #[inline]
+    pub const fn new(item: T) -> Self {
+        Self(item)
+    }
+
+    /// Returns the shared reference to the rejected item.
+    #[inline]
+    pub const fn item(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the rejected item.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        let CapacityError(item) = self;
+        item
+    }
+}
+
+// The item is not required to implement `Debug`, so that `Result::unwrap` can be used
+// with any item type.
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError: insufficient capacity")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> {}
+
+/// An error returned by the stack-shuffling operations of [`StackOps`](crate::StackOps).
+///
+/// The stack is left unchanged when the error is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StackOpError {
+    /// The stack has fewer items than the operation requires.
+    Underflow {
+        /// The number of items the operation requires.
+        required: usize,
+        /// The number of items in the stack.
+        available: usize,
+    },
+    /// The operation needs to push an item to a full stack.
+    Overflow,
+}
+
+impl fmt::Display for StackOpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StackOpError::Underflow {
+                required,
+                available,
+            } => write!(
+                f,
+                "stack underflow: {required} items required, {available} available"
+            ),
+            StackOpError::Overflow => f.write_str("stack overflow: insufficient capacity"),
+        }
+    }
+}
+
+impl core::error::Error for StackOpError {}
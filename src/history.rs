@@ -0,0 +1,446 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::Stack;
+
+/// A reversible operation recorded by [`UndoHistory`].
+pub trait Command {
+    /// The type of the object the command operates on.
+    type Target: ?Sized;
+
+    /// Applies the command to the target.
+    ///
+    /// This is called when the command is done for the first time and when it is redone.
+    fn apply(&mut self, target: &mut Self::Target);
+
+    /// Reverts the effect of [`Command::apply`] on the target.
+    fn revert(&mut self, target: &mut Self::Target);
+}
+
+/// A command recorded in the stacks of [`UndoHistory`].
+#[derive(Debug, Clone)]
+pub struct Step<C> {
+    command: C,
+    // Whether the command belongs to the same group as the command below it.
+    joined: bool,
+}
+
+impl<C> Step<C> {
+    /// Returns the shared reference to the recorded command.
+    pub fn command(&self) -> &C {
+        &self.command
+    }
+
+    /// Returns `true` if the command was done in the same group as the previous one,
+    /// so they are undone and redone together.
+    pub fn is_joined(&self) -> bool {
+        self.joined
+    }
+}
+
+/// An undo/redo history of [`Command`]s, backed by two stacks.
+///
+/// The done commands are kept on the undo stack `U` and the undone ones on the redo stack
+/// `R`. Doing a new command clears the redo stack. Commands done between
+/// [`UndoHistory::begin_group`] and [`UndoHistory::end_group`] form a group, which is undone
+/// and redone as a whole. Every method that counts history entries counts groups.
+///
+/// ## Notes
+///
+/// When the [maximum depth](UndoHistory::with_max_depth) is exceeded, the oldest group
+/// is removed from the bottom of the undo stack with [`Stack::s_remove_nth`], so
+/// [`VecDeque`](alloc::collections::VecDeque) is a better fit than [`Vec`] for
+/// a long bounded history.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{Command, UndoHistory};
+///
+/// struct Add(i32);
+///
+/// impl Command for Add {
+///     type Target = i32;
+///
+///     fn apply(&mut self, target: &mut i32) {
+///         *target += self.0;
+///     }
+///
+///     fn revert(&mut self, target: &mut i32) {
+///         *target -= self.0;
+///     }
+/// }
+///
+/// let mut value = 0;
+/// let mut history: UndoHistory<Add> = UndoHistory::new();
+/// history.do_(&mut value, Add(1));
+/// history.mark_clean();
+/// history.begin_group();
+/// history.do_(&mut value, Add(10));
+/// history.do_(&mut value, Add(100));
+/// history.end_group();
+/// assert_eq!((value, history.is_clean()), (111, false));
+///
+/// assert!(history.undo(&mut value));
+/// assert_eq!((value, history.is_clean()), (1, true));
+/// assert!(history.redo(&mut value));
+/// assert_eq!(value, 111);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UndoHistory<C, U = Vec<Step<C>>, R = U> {
+    undo: U,
+    redo: R,
+    undo_groups: usize,
+    redo_groups: usize,
+    max_depth: Option<usize>,
+    // The number of nested groups that are open, and whether the outermost one
+    // already has a command.
+    open_groups: usize,
+    group_started: bool,
+    // The number of undoable groups at the clean state, if it is reachable.
+    clean: Option<usize>,
+    command: PhantomData<fn() -> C>,
+}
+
+impl<C, U, R> UndoHistory<C, U, R>
+where
+    C: Command,
+    U: Stack<Item = Step<C>>,
+    R: Stack<Item = Step<C>>,
+{
+    /// Creates a new empty history with unlimited depth.
+    ///
+    /// The initial state is marked as clean.
+    pub fn new() -> Self
+    where
+        U: Default,
+        R: Default,
+    {
+        Self::from_stacks(U::default(), R::default())
+    }
+
+    /// Creates a new empty history that keeps at most `max_depth` groups for undoing.
+    pub fn with_max_depth(max_depth: usize) -> Self
+    where
+        U: Default,
+        R: Default,
+    {
+        let mut history = Self::new();
+        history.max_depth = Some(max_depth);
+        history
+    }
+
+    /// Creates a new history with unlimited depth from the empty undo and redo stacks.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if any of the stacks is not empty.
+    pub fn from_stacks(undo: U, redo: R) -> Self {
+        assert!(
+            undo.s_is_empty() && redo.s_is_empty(),
+            "the stacks of UndoHistory must be empty"
+        );
+        Self {
+            undo,
+            redo,
+            undo_groups: 0,
+            redo_groups: 0,
+            max_depth: None,
+            open_groups: 0,
+            group_started: false,
+            clean: Some(0),
+            command: PhantomData,
+        }
+    }
+
+    /// Returns the maximum number of groups kept for undoing, if any.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Sets the maximum number of groups kept for undoing, evicting the oldest ones
+    /// if there are more.
+    pub fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+        self.evict();
+    }
+
+    /// Returns the number of groups that can be undone.
+    pub fn undo_depth(&self) -> usize {
+        self.undo_groups
+    }
+
+    /// Returns the number of groups that can be redone.
+    pub fn redo_depth(&self) -> usize {
+        self.redo_groups
+    }
+
+    /// Returns `true` if there is a group to undo.
+    pub fn can_undo(&self) -> bool {
+        self.undo_groups != 0
+    }
+
+    /// Returns `true` if there is a group to redo.
+    pub fn can_redo(&self) -> bool {
+        self.redo_groups != 0
+    }
+
+    /// Returns the undo stack, with the most recently done command on top.
+    pub fn undo_stack(&self) -> &U {
+        &self.undo
+    }
+
+    /// Returns the redo stack, with the most recently undone command on top.
+    pub fn redo_stack(&self) -> &R {
+        &self.redo
+    }
+
+    /// Applies the command to the target and records it.
+    ///
+    /// The redo stack is cleared. Inside a group, the command joins the commands
+    /// done since [`UndoHistory::begin_group`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the undo stack is full.
+    pub fn do_(&mut self, target: &mut C::Target, mut command: C) {
+        command.apply(target);
+        self.redo.s_truncate(0);
+        self.redo_groups = 0;
+        if self.clean.is_some_and(|clean| clean > self.undo_groups) {
+            self.clean = None;
+        }
+
+        let joined = self.open_groups != 0 && self.group_started;
+        if joined {
+            // The clean state was in the middle of the group, so undoing can't return to it.
+            if self.clean == Some(self.undo_groups) {
+                self.clean = None;
+            }
+        } else {
+            self.undo_groups += 1;
+            self.group_started = self.open_groups != 0;
+        }
+        self.undo.s_push(Step { command, joined });
+        if !joined {
+            self.evict();
+        }
+    }
+
+    /// Reverts the most recently done group and moves it to the redo stack.
+    ///
+    /// Any open group is closed first. Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self, target: &mut C::Target) -> bool {
+        self.close_groups();
+        if self.undo_groups == 0 {
+            return false;
+        }
+        while let Some(mut step) = self.undo.s_pop() {
+            step.command.revert(target);
+            let joined = step.joined;
+            self.redo.s_push(step);
+            if !joined {
+                break;
+            }
+        }
+        self.undo_groups -= 1;
+        self.redo_groups += 1;
+        true
+    }
+
+    /// Applies the most recently undone group again and moves it to the undo stack.
+    ///
+    /// Any open group is closed first. Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self, target: &mut C::Target) -> bool {
+        self.close_groups();
+        let Some(mut step) = self.redo.s_pop() else {
+            return false;
+        };
+        loop {
+            step.command.apply(target);
+            self.undo.s_push(step);
+            match self.redo.pop_if(|step| step.joined) {
+                Some(next) => step = next,
+                None => break,
+            }
+        }
+        self.redo_groups -= 1;
+        self.undo_groups += 1;
+        self.evict();
+        true
+    }
+
+    /// Opens a group, so that the commands done until the matching
+    /// [`UndoHistory::end_group`] are undone and redone together.
+    ///
+    /// Groups can be nested, in which case the outermost group wins.
+    pub fn begin_group(&mut self) {
+        if self.open_groups == 0 {
+            self.group_started = false;
+        }
+        self.open_groups += 1;
+    }
+
+    /// Closes the group opened by the matching [`UndoHistory::begin_group`].
+    ///
+    /// Does nothing if there is no open group.
+    pub fn end_group(&mut self) {
+        self.open_groups = self.open_groups.saturating_sub(1);
+    }
+
+    /// Marks the current state as clean, for example, after saving the document.
+    pub fn mark_clean(&mut self) {
+        self.clean = Some(self.undo_groups);
+    }
+
+    /// Returns `true` if the current state is the one marked as clean.
+    ///
+    /// The initial state is clean until another state is marked.
+    pub fn is_clean(&self) -> bool {
+        self.clean == Some(self.undo_groups)
+    }
+
+    /// Clears both stacks without touching the target.
+    ///
+    /// The current state becomes clean.
+    pub fn clear(&mut self) {
+        self.undo.s_truncate(0);
+        self.redo.s_truncate(0);
+        self.undo_groups = 0;
+        self.redo_groups = 0;
+        self.close_groups();
+        self.clean = Some(0);
+    }
+
+    fn close_groups(&mut self) {
+        self.open_groups = 0;
+        self.group_started = false;
+    }
+
+    // Removes the oldest groups from the bottom of the undo stack until the depth is
+    // within the limit.
+    fn evict(&mut self) {
+        let Some(max_depth) = self.max_depth else {
+            return;
+        };
+        while self.undo_groups > max_depth {
+            // The bottom command starts the oldest group, followed by the joined ones.
+            loop {
+                self.undo.s_remove_nth(self.undo.s_len() - 1);
+                let bottom = self.undo.s_len().checked_sub(1);
+                if !bottom
+                    .and_then(|n| self.undo.peek_nth(n))
+                    .is_some_and(|step| step.joined)
+                {
+                    break;
+                }
+            }
+            self.undo_groups -= 1;
+            self.clean = match self.clean {
+                Some(0) | None => None,
+                Some(clean) => Some(clean - 1),
+            };
+        }
+        // The open group was evicted as a whole, so the next command starts it again
+        // instead of joining a command that is gone.
+        if self.undo_groups == 0 {
+            self.group_started = false;
+        }
+    }
+}
+
+impl<C, U, R> Default for UndoHistory<C, U, R>
+where
+    C: Command,
+    U: Stack<Item = Step<C>> + Default,
+    R: Stack<Item = Step<C>> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{collections::VecDeque, vec, vec::Vec};
+
+    struct Push(char);
+
+    impl Command for Push {
+        type Target = Vec<char>;
+
+        fn apply(&mut self, target: &mut Vec<char>) {
+            target.push(self.0);
+        }
+
+        fn revert(&mut self, target: &mut Vec<char>) {
+            assert_eq!(target.pop(), Some(self.0));
+        }
+    }
+
+    #[test]
+    fn groups_depth_and_markers() {
+        let mut text = Vec::new();
+        let mut history: UndoHistory<Push, VecDeque<_>, Vec<_>> = UndoHistory::with_max_depth(2);
+        history.do_(&mut text, Push('a'));
+        history.begin_group();
+        history.do_(&mut text, Push('b'));
+        history.begin_group();
+        history.do_(&mut text, Push('c'));
+        history.end_group();
+        history.end_group();
+        history.mark_clean();
+        history.do_(&mut text, Push('d'));
+        // 'a' was evicted to keep two groups.
+        assert_eq!((history.undo_depth(), history.undo_stack().len()), (2, 3));
+
+        assert!(history.undo(&mut text));
+        assert!(history.is_clean());
+        assert!(history.undo(&mut text));
+        assert_eq!(text, vec!['a']);
+        assert!(!history.undo(&mut text));
+        assert!(history.redo(&mut text));
+        assert_eq!(
+            (text.as_slice(), history.is_clean()),
+            (&['a', 'b', 'c'][..], true)
+        );
+
+        // Doing a command drops the redo stack together with the clean state in it.
+        assert!(history.undo(&mut text));
+        history.do_(&mut text, Push('e'));
+        assert!(!history.can_redo());
+        assert!(!history.is_clean());
+        assert_eq!(text, vec!['a', 'e']);
+
+        history.set_max_depth(Some(0));
+        assert!(!history.can_undo());
+        assert_eq!(text, vec!['a', 'e']);
+    }
+
+    #[test]
+    fn groups_evicted_while_open() {
+        let mut text = Vec::new();
+        let mut history: UndoHistory<Push> = UndoHistory::with_max_depth(0);
+        history.begin_group();
+        history.do_(&mut text, Push('a'));
+        history.do_(&mut text, Push('b'));
+        assert_eq!((history.undo_depth(), history.undo_stack().len()), (0, 0));
+        history.end_group();
+
+        history.set_max_depth(None);
+        history.begin_group();
+        history.do_(&mut text, Push('c'));
+        history.set_max_depth(Some(0));
+        history.set_max_depth(None);
+        history.do_(&mut text, Push('d'));
+        history.do_(&mut text, Push('e'));
+        history.end_group();
+        assert_eq!((history.undo_depth(), history.undo_stack().len()), (1, 2));
+        assert!(!history.undo_stack()[0].is_joined());
+
+        assert!(history.undo(&mut text));
+        assert_eq!(text, vec!['a', 'b', 'c']);
+        assert!(!history.can_undo());
+    }
+}
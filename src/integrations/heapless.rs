@@ -0,0 +1,115 @@
+use core::{iter::Rev, mem::MaybeUninit, slice};
+
+use heapless::{LenType, Vec};
+
+use crate::{index_from_depth, BoundedStack, CapacityError, ContiguousStack, LIFOEntry, Stack};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<T, LenT: LenType, const N: usize> Stack for Vec<T, N, LenT> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, T>>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, T>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        if self.push(item).is_err() {
+            panic!("heapless::Vec is full (capacity is {N})");
+        }
+    }
+
+    #[inline]
+    fn s_try_push(&mut self, item: Self::Item) -> Result<(), CapacityError<Self::Item>> {
+        self.push(item).map_err(CapacityError::new)
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // We just pushed to the vector, so the vector is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn lifo_push_with<F: FnOnce() -> Self::Item>(&mut self, f: F) -> LIFOEntry<'_, Self> {
+        self.lifo_push_uninit().write(f())
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.pop()
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.last()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.last_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.iter().rev().nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.iter_mut().rev().nth(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = index_from_depth(self.len(), n)?;
+        Some(self.remove(index))
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.iter().rev()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.iter_mut().rev()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<T, LenT: LenType, const N: usize> BoundedStack for Vec<T, N, LenT> {
+    #[inline]
+    fn remaining_capacity(&self) -> usize {
+        N - self.len()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+unsafe impl<T, LenT: LenType, const N: usize> ContiguousStack for Vec<T, N, LenT> {
+    #[inline]
+    fn spare_top(&mut self) -> Option<&mut MaybeUninit<Self::Item>> {
+        self.spare_capacity_mut().first_mut()
+    }
+
+    #[inline]
+    unsafe fn assume_pushed(&mut self) {
+        self.set_len(self.len() + 1);
+    }
+}
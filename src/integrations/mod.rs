@@ -0,0 +1,41 @@
+//! Implementations of [`Stack`](crate::Stack) for the containers from third-party crates
+//! and of the `serde` traits for the stacks provided by the crate.
+//!
+//! Each integration is gated behind the cargo feature named after the crate.
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "tinyvec")]
+mod tinyvec;
+
+#[cfg(all(test, feature = "arrayvec", feature = "heapless", feature = "tinyvec"))]
+mod tests {
+    use crate::BoundedStack;
+
+    fn fill<S: BoundedStack<Item = u8>>(stack: &mut S) -> u8 {
+        let mut next = 0;
+        loop {
+            match stack.try_lifo_push(next) {
+                Ok(entry) => assert_eq!(*entry, next),
+                Err(err) => {
+                    assert!(stack.is_full());
+                    return err.into_inner();
+                }
+            }
+            next += 1;
+        }
+    }
+
+    #[test]
+    fn overflow_hands_item_back() {
+        assert_eq!(fill(&mut arrayvec::ArrayVec::<u8, 3>::new()), 3);
+        assert_eq!(fill(&mut heapless::Vec::<u8, 3>::new()), 3);
+        assert_eq!(fill(&mut tinyvec::ArrayVec::<[u8; 3]>::new()), 3);
+    }
+}
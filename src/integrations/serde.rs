@@ -0,0 +1,315 @@
+//! `Serialize` and `Deserialize` implementations for the stacks provided by the crate.
+//!
+//! The stacks are represented as sequences of their items, from the bottom to the top.
+//! Deserialization goes through the regular pushes, so the capacity of [`ArrayStack`] is
+//! checked and the cached extrema and aggregates are recomputed rather than trusted.
+//! [`TwoStackQueue`] is represented as the sequence of its items from the front to the back,
+//! regardless of how they are split between its stacks, and is deserialized into the back
+//! stack. [`LockedStack`] and [`RwLockedStack`] are represented as the stacks they protect,
+//! which are locked for the serialization.
+//!
+//! `TreiberStack` has no implementations, since it can't be read consistently while other
+//! threads push and pop. Neither has `Journaled`, since its log is meaningful only together
+//! with the state of the stack before the recorded operations. Serialize the parts
+//! returned by `Journaled::into_parts` instead.
+
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, IgnoredAny, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(feature = "alloc")]
+use crate::{AggStack, MinMaxStack, Monoid, PersistentStack};
+use crate::{ArrayStack, Stack, TwoStackQueue};
+#[cfg(feature = "std")]
+use crate::{LockedStack, RwLockedStack};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+impl<T: Serialize, const N: usize> Serialize for ArrayStack<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+struct ArrayStackVisitor<T, const N: usize>(PhantomData<fn() -> T>);
+
+impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayStackVisitor<T, N> {
+    type Value = ArrayStack<T, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of at most {N} items")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut stack = ArrayStack::new();
+        while let Some(item) = seq.next_element()? {
+            if stack.s_try_push(item).is_err() {
+                // Count the rest of the items to report the actual length.
+                let mut len = N + 1;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    len += 1;
+                }
+                return Err(de::Error::invalid_length(len, &self));
+            }
+        }
+        Ok(stack)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for ArrayStack<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ArrayStackVisitor(PhantomData))
+    }
+}
+
+impl<S, F> Serialize for TwoStackQueue<S, F>
+where
+    S: Stack,
+    F: Stack<Item = S::Item>,
+    S::Item: Serialize,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let (front, back) = self.parts();
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for item in front.iter_lifo() {
+            seq.serialize_element(item)?;
+        }
+        for n in (0..back.s_len()).rev() {
+            // SAFETY: The depth is less than the length of the back stack.
+            seq.serialize_element(unsafe { back.peek_nth_unchecked(n) })?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, S, F> Deserialize<'de> for TwoStackQueue<S, F>
+where
+    S: Stack + Deserialize<'de>,
+    F: Stack<Item = S::Item> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        S::deserialize(deserializer).map(|back| Self::from_parts(F::default(), back))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serialize> Serialize for PersistentStack<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let items: Vec<&T> = self.iter().collect();
+        serializer.collect_seq(items.iter().rev())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for PersistentStack<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|items| items.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serialize> Serialize for MinMaxStack<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T: Deserialize<'de> + Ord> Deserialize<'de> for MinMaxStack<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|items| items.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serialize, M: Monoid<T>> Serialize for AggStack<T, M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T: Deserialize<'de>, M: Monoid<T>> Deserialize<'de> for AggStack<T, M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|items| items.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Stack + Serialize> Serialize for LockedStack<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let stack = self
+            .lock()
+            .map_err(|_| serde::ser::Error::custom(POISONED))?;
+        stack.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, C: Stack + Deserialize<'de>> Deserialize<'de> for LockedStack<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        C::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Stack + Serialize> Serialize for RwLockedStack<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let stack = self
+            .read()
+            .map_err(|_| serde::ser::Error::custom(POISONED))?;
+        stack.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, C: Stack + Deserialize<'de>> Deserialize<'de> for RwLockedStack<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        C::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "std")]
+const POISONED: &str = "the lock of the stack is poisoned";
+
+#[cfg(test)]
+mod tests {
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn bottom_to_top_sequences() {
+        let mut stack: ArrayStack<u8, 2> = ArrayStack::new();
+        stack.s_extend([1, 2]);
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::SeqEnd,
+        ];
+        assert_ser_tokens(&stack, &tokens);
+        assert_de_tokens_error::<ArrayStack<u8, 1>>(
+            &tokens,
+            "invalid length 2, expected a sequence of at most 1 items",
+        );
+        assert_de_tokens_error::<ArrayStack<u8, 1>>(
+            &[
+                Token::Seq { len: None },
+                Token::U8(1),
+                Token::U8(2),
+                Token::U8(3),
+                Token::SeqEnd,
+            ],
+            "invalid length 3, expected a sequence of at most 1 items",
+        );
+        let items = SeqDeserializer::<_, Error>::new([3u8, 4].into_iter());
+        let stack = ArrayStack::<u8, 2>::deserialize(items).unwrap();
+        assert_eq!(stack.as_slice(), &[3, 4]);
+    }
+
+    #[test]
+    fn queue_from_front_to_back() {
+        let mut queue = TwoStackQueue::<ArrayStack<u8, 2>>::new();
+        queue.push_back(1);
+        queue.push_back(2);
+        queue.pop_front();
+        queue.push_back(3);
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::U8(2),
+            Token::U8(3),
+            Token::SeqEnd,
+        ];
+        // The front stack holds 2 and the back stack holds 3.
+        assert_ser_tokens(&queue, &tokens);
+        let mut queue = TwoStackQueue::<ArrayStack<u8, 2>>::new();
+        queue.push_back(2);
+        queue.push_back(3);
+        assert_ser_tokens(&queue, &tokens);
+
+        let items = SeqDeserializer::<_, Error>::new([2u8, 3].into_iter());
+        let mut queue = TwoStackQueue::<ArrayStack<u8, 2>>::deserialize(items).unwrap();
+        assert_eq!(queue.pop_front(), Some(2));
+        assert_eq!(queue.pop_front(), Some(3));
+        let items = SeqDeserializer::<_, Error>::new([1u8, 2, 3].into_iter());
+        assert!(TwoStackQueue::<ArrayStack<u8, 2>>::deserialize(items).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn recomputed_invariants() {
+        let stack = PersistentStack::new().push(1u8).push(2);
+        assert_ser_tokens(
+            &stack,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::SeqEnd,
+            ],
+        );
+        let items = SeqDeserializer::<_, Error>::new([1u8, 2].into_iter());
+        assert_eq!(PersistentStack::deserialize(items).unwrap(), stack);
+
+        let items = SeqDeserializer::<_, Error>::new([4u8, 1, 9].into_iter());
+        let stack = MinMaxStack::<u8>::deserialize(items).unwrap();
+        assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&9)));
+
+        struct Sum;
+
+        impl Monoid<u32> for Sum {
+            type Agg = u32;
+
+            fn identity() -> u32 {
+                0
+            }
+
+            fn lift(item: &u32) -> u32 {
+                *item
+            }
+
+            fn combine(a: &u32, b: &u32) -> u32 {
+                a + b
+            }
+        }
+
+        let items = SeqDeserializer::<_, Error>::new([4u32, 1, 9].into_iter());
+        let stack = AggStack::<u32, Sum>::deserialize(items).unwrap();
+        assert_eq!(stack.aggregate(), 14);
+        let items = SeqDeserializer::<_, Error>::new([4u32, 1, 9].into_iter());
+        let mut queue = crate::AggQueue::<u32, Sum>::deserialize(items).unwrap();
+        assert_eq!(queue.aggregate(), 14);
+        queue.pop_front();
+        assert_eq!(queue.aggregate(), 10);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn locked_stacks() {
+        use alloc::vec;
+
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::SeqEnd,
+        ];
+        let stack = LockedStack::new(vec![1u8, 2]);
+        assert_ser_tokens(&stack, &tokens);
+        let stack = RwLockedStack::new(vec![1u8, 2]);
+        assert_ser_tokens(&stack, &tokens);
+
+        let items = SeqDeserializer::<_, Error>::new([3u8, 4].into_iter());
+        let stack = LockedStack::<Vec<u8>>::deserialize(items).unwrap();
+        assert_eq!(stack.into_inner().unwrap(), [3, 4]);
+        let items = SeqDeserializer::<_, Error>::new([3u8, 4].into_iter());
+        let stack = RwLockedStack::<Vec<u8>>::deserialize(items).unwrap();
+        assert_eq!(stack.into_inner().unwrap(), [3, 4]);
+    }
+}
@@ -0,0 +1,84 @@
+use core::{iter::Rev, slice};
+
+use smallvec::{Array, SmallVec};
+
+use crate::{index_from_depth, LIFOEntry, Stack};
+
+// `SmallVec` spills to the heap instead of overflowing, so the default
+// `Stack::s_try_push` that never fails is correct for it.
+#[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+impl<A: Array> Stack for SmallVec<A> {
+    type Item = A::Item;
+
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, A::Item>>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, A::Item>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.push(item);
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.push(item);
+        // We just pushed to the vector, so the vector is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.pop()
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.last()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.last_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.iter().rev().nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.iter_mut().rev().nth(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = index_from_depth(self.len(), n)?;
+        Some(self.remove(index))
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.iter().rev()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.iter_mut().rev()
+    }
+}
@@ -0,0 +1,143 @@
+use core::iter::FusedIterator;
+
+use crate::{LIFOEntry, Stack};
+
+/// An iterator that pops up to a given number of items from the stack in LIFO order.
+///
+/// This is constructed with [`Stack::s_drain_top`]. If it is dropped before it is exhausted,
+/// the remaining items are popped and dropped as well.
+pub struct DrainTop<'a, C: ?Sized + Stack> {
+    stack: &'a mut C,
+    remaining: usize,
+}
+
+impl<'a, C: ?Sized + Stack> DrainTop<'a, C> {
+    /// Creates a new iterator that pops up to `remaining` items from the stack.
+    pub(crate) fn new(stack: &'a mut C, remaining: usize) -> Self {
+        Self { stack, remaining }
+    }
+}
+
+impl<C: ?Sized + Stack> Iterator for DrainTop<'_, C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.stack.s_pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<C: ?Sized + Stack> ExactSizeIterator for DrainTop<'_, C> {}
+
+impl<C: ?Sized + Stack> FusedIterator for DrainTop<'_, C> {}
+
+impl<C: ?Sized + Stack> Drop for DrainTop<'_, C> {
+    fn drop(&mut self) {
+        self.stack.s_pop_n(self.remaining);
+    }
+}
+
+/// An iterator that pops the items from the stack in LIFO order until the stack is empty.
+///
+/// This is constructed with [`Stack::pop_iter`].
+pub struct PopIter<'a, C: ?Sized> {
+    stack: &'a mut C,
+}
+
+impl<'a, C: ?Sized + Stack> PopIter<'a, C> {
+    /// Creates a new iterator that pops the items from the stack.
+    pub(crate) fn new(stack: &'a mut C) -> Self {
+        Self { stack }
+    }
+}
+
+impl<C: ?Sized + Stack> Iterator for PopIter<'_, C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.s_pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.stack.s_len();
+        (len, Some(len))
+    }
+}
+
+impl<C: ?Sized + Stack> ExactSizeIterator for PopIter<'_, C> {}
+
+impl<C: ?Sized + Stack> FusedIterator for PopIter<'_, C> {}
+
+/// An iterator that pops the items from the stack in LIFO order while they satisfy
+/// the predicate.
+///
+/// This is constructed with [`Stack::pop_while`].
+pub struct PopWhile<'a, C: ?Sized, P> {
+    stack: &'a mut C,
+    predicate: P,
+    done: bool,
+}
+
+impl<'a, C: ?Sized + Stack, P> PopWhile<'a, C, P> {
+    /// Creates a new iterator that pops the items from the stack while they satisfy
+    /// the predicate.
+    pub(crate) fn new(stack: &'a mut C, predicate: P) -> Self {
+        Self {
+            stack,
+            predicate,
+            done: false,
+        }
+    }
+
+    /// Returns the "entry" object corresponding to the top element of the stack
+    /// or `None` if the stack is empty.
+    ///
+    /// Once the iterator is exhausted, this is the first item that did not satisfy
+    /// the predicate.
+    pub fn into_lifo(self) -> Option<LIFOEntry<'a, C>> {
+        self.stack.lifo()
+    }
+}
+
+impl<C: ?Sized + Stack, P: FnMut(&mut C::Item) -> bool> Iterator for PopWhile<'_, C, P> {
+    type Item = C::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let Some(mut entry) = self.stack.lifo() else {
+            self.done = true;
+            return None;
+        };
+        if (self.predicate)(&mut entry) {
+            Some(entry.pop_pointee())
+        } else {
+            self.done = true;
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.stack.s_len()))
+        }
+    }
+}
+
+impl<C: ?Sized + Stack, P: FnMut(&mut C::Item) -> bool> FusedIterator for PopWhile<'_, C, P> {}
@@ -0,0 +1,316 @@
+use alloc::vec::Vec;
+use core::{mem, ops::Deref};
+
+use crate::{CapacityError, LIFOEntry, Stack, StackOpError};
+
+/// An operation recorded by [`Journaled`].
+///
+/// Depths are counted from the top of the stack, where the top element is at depth `0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Op<T> {
+    /// The item was pushed to the stack.
+    Push(T),
+    /// The top item was popped from the stack.
+    Pop,
+    /// The item at the given depth was removed from the stack.
+    RemoveNth(usize),
+    /// The item at the given depth was borrowed mutably and ended up with the given value.
+    Set {
+        /// The depth of the item.
+        depth: usize,
+        /// The value of the item after the mutation.
+        value: T,
+    },
+}
+
+/// A stack wrapper that records the operations done on the stack into a log of [`Op`]s,
+/// which can be reproduced with [`replay`].
+///
+/// It implements [`Stack`] itself, so the operations must be done through it.
+/// Pushes, pops and removals are recorded as they happen. An item borrowed mutably,
+/// for example through a [`LIFOEntry`], is recorded with its new value as [`Op::Set`]
+/// before the next operation or when the log is read.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{replay, Journaled, Stack};
+///
+/// let mut stack = Journaled::new(vec![1]);
+/// stack.s_push(2);
+/// *stack.lifo().unwrap() += 10;
+/// stack.s_pop();
+/// stack.s_push(3);
+///
+/// let (stack, log) = stack.into_parts();
+/// let mut copy = vec![1];
+/// replay(&log, &mut copy).unwrap();
+/// assert_eq!(copy, stack);
+/// ```
+pub struct Journaled<C: Stack> {
+    stack: C,
+    log: Vec<Op<C::Item>>,
+    // The indices, counted from the bottom, of the items that were borrowed mutably
+    // and are not recorded yet.
+    pending: Vec<usize>,
+    pending_all: bool,
+}
+
+impl<C: Stack> Journaled<C>
+where
+    C::Item: Clone,
+{
+    /// Wraps the stack with an empty log.
+    ///
+    /// The log can be replayed on a stack equal to the wrapped one.
+    pub fn new(stack: C) -> Self {
+        Self {
+            stack,
+            log: Vec::new(),
+            pending: Vec::new(),
+            pending_all: false,
+        }
+    }
+
+    /// Returns the recorded operations.
+    pub fn log(&mut self) -> &[Op<C::Item>] {
+        self.flush();
+        &self.log
+    }
+
+    /// Takes the recorded operations out, leaving the log empty.
+    ///
+    /// The subsequent operations can be replayed on the stack in its current state.
+    pub fn take_log(&mut self) -> Vec<Op<C::Item>> {
+        self.flush();
+        mem::take(&mut self.log)
+    }
+
+    /// Returns the stack and the recorded operations.
+    pub fn into_parts(mut self) -> (C, Vec<Op<C::Item>>) {
+        self.flush();
+        (self.stack, self.log)
+    }
+
+    // Records the mutably borrowed items with their current values.
+    fn flush(&mut self) {
+        let len = self.stack.s_len();
+        if mem::take(&mut self.pending_all) {
+            self.pending.clear();
+            self.pending.extend(0..len);
+        }
+        for index in self.pending.drain(..) {
+            let depth = len - 1 - index;
+            // SAFETY: The items are borrowed only at existing indices and the pending
+            // indices are flushed before the stack shrinks, so the depth is in bounds.
+            let value = unsafe { self.stack.peek_nth_unchecked(depth) }.clone();
+            self.log.push(Op::Set { depth, value });
+        }
+    }
+
+    fn mark_pending(&mut self, depth: usize) {
+        let index = self.stack.s_len() - 1 - depth;
+        if !self.pending.contains(&index) {
+            self.pending.push(index);
+        }
+    }
+}
+
+impl<C: Stack> Deref for Journaled<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stack
+    }
+}
+
+impl<C: Stack> Stack for Journaled<C>
+where
+    C::Item: Clone,
+{
+    type Item = C::Item;
+
+    type IterLifo<'a>
+        = C::IterLifo<'a>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = C::IterLifoMut<'a>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.stack.s_is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.stack.s_len()
+    }
+
+    fn s_push(&mut self, item: Self::Item) {
+        self.flush();
+        self.stack.s_push(item.clone());
+        self.log.push(Op::Push(item));
+    }
+
+    fn s_try_push(&mut self, item: Self::Item) -> Result<(), CapacityError<Self::Item>> {
+        self.flush();
+        self.stack.s_try_push(item.clone())?;
+        self.log.push(Op::Push(item));
+        Ok(())
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // SAFETY: We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.flush();
+        let item = self.stack.s_pop()?;
+        self.log.push(Op::Pop);
+        Some(item)
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.stack.lifo_ref()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peek_nth_mut(0)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.stack.iter_lifo()
+    }
+
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.flush();
+        self.pending_all = true;
+        self.stack.iter_lifo_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.stack.peek_nth(n)
+    }
+
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.flush();
+        if n < self.stack.s_len() {
+            self.mark_pending(n);
+        }
+        self.stack.peek_nth_mut(n)
+    }
+
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.flush();
+        let item = self.stack.s_remove_nth(n)?;
+        self.log.push(Op::RemoveNth(n));
+        Some(item)
+    }
+}
+
+/// Applies the operations recorded by [`Journaled`] to the stack.
+///
+/// Replaying the log on a stack equal to the one [`Journaled`] started with reproduces
+/// the final state of the journaled stack.
+///
+/// ## Errors
+///
+/// Returns [`StackOpError::Underflow`] if an operation refers to an item the stack
+/// doesn't have and [`StackOpError::Overflow`] if a push doesn't fit. The operations
+/// before the failed one remain applied.
+pub fn replay<C>(log: &[Op<C::Item>], stack: &mut C) -> Result<(), StackOpError>
+where
+    C: ?Sized + Stack,
+    C::Item: Clone,
+{
+    for op in log {
+        let available = stack.s_len();
+        let underflow = |required| StackOpError::Underflow {
+            required,
+            available,
+        };
+        match op {
+            Op::Push(item) => stack
+                .s_try_push(item.clone())
+                .map_err(|_| StackOpError::Overflow)?,
+            Op::Pop => {
+                stack.s_pop().ok_or(underflow(1))?;
+            }
+            Op::RemoveNth(n) => {
+                stack.s_remove_nth(*n).ok_or(underflow(n + 1))?;
+            }
+            Op::Set { depth, value } => {
+                *stack.peek_nth_mut(*depth).ok_or(underflow(depth + 1))? = value.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArrayStack;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn replay_matches_vec() {
+        let mut journaled = Journaled::new(vec![10, 20]);
+        let mut reference = vec![10, 20];
+        for i in 0..50 {
+            match i % 5 {
+                0 | 1 => {
+                    journaled.s_push(i);
+                    reference.push(i);
+                }
+                2 => {
+                    *journaled.lifo_push(i) *= 2;
+                    reference.push(i * 2);
+                }
+                3 => {
+                    assert_eq!(
+                        journaled.s_remove_nth(1),
+                        Some(reference.remove(reference.len() - 2))
+                    );
+                    *journaled.peek_nth_mut(2).unwrap() += 1;
+                    let len = reference.len();
+                    reference[len - 3] += 1;
+                }
+                _ => {
+                    assert_eq!(journaled.s_pop(), reference.pop());
+                    journaled.iter_lifo_mut().for_each(|item| *item -= 1);
+                    reference.iter_mut().for_each(|item| *item -= 1);
+                }
+            }
+        }
+        assert_eq!(journaled.s_remove_nth(100), None);
+        let (stack, log) = journaled.into_parts();
+        assert_eq!(stack, reference);
+
+        let mut replayed = vec![10, 20];
+        replay(&log, &mut replayed).unwrap();
+        assert_eq!(replayed, reference);
+
+        let mut short: ArrayStack<i32, 4> = ArrayStack::new();
+        short.s_extend([10, 20]);
+        assert_eq!(replay(&log, &mut short), Err(StackOpError::Overflow));
+        assert_eq!(
+            replay(&[Op::Pop], &mut Vec::<i32>::new()),
+            Err(StackOpError::Underflow {
+                required: 1,
+                available: 0
+            })
+        );
+    }
+}
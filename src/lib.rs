@@ -1,4 +1,88 @@
+#![no_std]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{vec_deque, VecDeque},
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use core::{iter::Rev, slice};
+
+#[cfg(feature = "alloc")]
+mod agg;
+mod array_stack;
+mod concurrent;
+mod depth_entry;
+mod error;
+#[cfg(feature = "alloc")]
+mod history;
+mod integrations;
+mod iter;
+#[cfg(feature = "alloc")]
+mod journal;
+#[cfg(feature = "std")]
+mod locked;
+#[cfg(feature = "alloc")]
+mod min_max;
+#[cfg(feature = "alloc")]
+mod persistent;
+#[cfg(feature = "alloc")]
+mod prefix;
+mod queue;
+mod stack_ops;
+mod tentative;
+mod top_entry;
+#[cfg(feature = "alloc")]
+mod txn;
+mod uninit;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use agg::{AggStack, Dual, Monoid};
+pub use array_stack::ArrayStack;
+pub use concurrent::ConcurrentStack;
+#[cfg(feature = "concurrent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "concurrent")))]
+pub use concurrent::TreiberStack;
+pub use depth_entry::DepthEntry;
+pub use error::{CapacityError, StackOpError};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use history::{Command, Step, UndoHistory};
+pub use iter::{DrainTop, PopIter, PopWhile};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use journal::{replay, Journaled, Op};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use locked::{LockedLIFOEntry, LockedLIFORef, LockedStack, RwLockedStack};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use min_max::MinMaxStack;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use persistent::{PersistentIter, PersistentIterMut, PersistentStack};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use queue::AggQueue;
+pub use queue::TwoStackQueue;
+pub use stack_ops::StackOps;
+pub use tentative::TentativeEntry;
+pub use top_entry::{TopEntry, VacantTop};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use txn::StackTxn;
+pub use uninit::{ContiguousStack, UninitEntry};
+
 /// A convenience type alias that should be easier to read and understand.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type LIFOVecEntry<'a, T> = LIFOEntry<'a, Vec<T>>;
 
 /// An "entry" object corresponding to the top element of the stack.
@@ -26,9 +110,86 @@ impl<'a, C: ?Sized + Stack> LIFOEntry<'a, C> {
         // existence of the LIFOEntry object, so the call is safe.
         unsafe { stack.s_pop_unchecked() }
     }
+
+    /// Converts the entry into a shared reference to the LIFO element with the lifetime
+    /// of the entry.
+    pub fn into_ref(self) -> &'a C::Item {
+        let LIFOEntry(stack) = self;
+        // SAFETY: The stack is not empty by the virtue of
+        // existence of the LIFOEntry object, so the call is safe.
+        unsafe { stack.lifo_ref_unchecked() }
+    }
+
+    /// Converts the entry into a mutable reference to the LIFO element with the lifetime
+    /// of the entry.
+    ///
+    /// This allows returning the reference to the element from a function that obtained the entry.
+    pub fn into_mut(self) -> &'a mut C::Item {
+        let LIFOEntry(stack) = self;
+        // SAFETY: The stack is not empty by the virtue of
+        // existence of the LIFOEntry object, so the call is safe.
+        unsafe { stack.lifo_mut_unchecked() }
+    }
+
+    /// Converts the entry into the mutable reference to the stack.
+    pub fn into_stack(self) -> &'a mut C {
+        let LIFOEntry(stack) = self;
+        stack
+    }
+
+    /// Returns the shared reference to the stack.
+    pub fn as_stack(&self) -> &C {
+        let LIFOEntry(stack) = self;
+        stack
+    }
+
+    /// Replaces the LIFO element with the given value and returns the old one.
+    pub fn replace(&mut self, value: C::Item) -> C::Item {
+        core::mem::replace(&mut **self, value)
+    }
+
+    /// Replaces the LIFO element with its default value and returns the old one.
+    pub fn take(&mut self) -> C::Item
+    where
+        C::Item: Default,
+    {
+        core::mem::take(&mut **self)
+    }
+
+    /// Swaps the LIFO element with the LIFO element of another stack.
+    pub fn swap_with<D>(&mut self, other: &mut LIFOEntry<'_, D>)
+    where
+        D: ?Sized + Stack<Item = C::Item>,
+    {
+        core::mem::swap(&mut **self, &mut **other)
+    }
+
+    /// Replaces the LIFO element with the result of applying `f` to it.
+    ///
+    /// ## Notes
+    ///
+    /// The element is popped for the duration of the call, so if `f` panics,
+    /// the element is no longer on the stack.
+    pub fn map_in_place(self, f: impl FnOnce(C::Item) -> C::Item) -> Self {
+        let LIFOEntry(stack) = self;
+        // SAFETY: The stack is not empty by the virtue of
+        // existence of the LIFOEntry object, so the call is safe.
+        let item = unsafe { stack.s_pop_unchecked() };
+        // The popped item freed a slot, so the push can't overflow a bounded stack.
+        stack.lifo_push(f(item))
+    }
+
+    /// Returns a new "entry" object for the same element that borrows this entry.
+    ///
+    /// This is useful for passing the entry to a function that consumes it
+    /// without giving up the original entry.
+    pub fn reborrow(&mut self) -> LIFOEntry<'_, C> {
+        let LIFOEntry(stack) = self;
+        LIFOEntry(stack)
+    }
 }
 
-impl<'a, C: ?Sized + Stack> std::ops::Deref for LIFOEntry<'a, C> {
+impl<'a, C: ?Sized + Stack> core::ops::Deref for LIFOEntry<'a, C> {
     type Target = C::Item;
 
     fn deref(&self) -> &Self::Target {
@@ -38,7 +199,7 @@ impl<'a, C: ?Sized + Stack> std::ops::Deref for LIFOEntry<'a, C> {
     }
 }
 
-impl<'a, C: ?Sized + Stack> std::ops::DerefMut for LIFOEntry<'a, C> {
+impl<'a, C: ?Sized + Stack> core::ops::DerefMut for LIFOEntry<'a, C> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         let LIFOEntry(stack) = self;
         // SAFETY: The stack is not empty, so the call is safe.
@@ -53,13 +214,34 @@ pub trait Stack {
     /// The type of the items stored in the stack.
     type Item;
 
+    /// The type of the iterator over shared references to the items,
+    /// from the top to the bottom of the stack.
+    type IterLifo<'a>: Iterator<Item = &'a Self::Item>
+    where
+        Self: 'a;
+
+    /// The type of the iterator over mutable references to the items,
+    /// from the top to the bottom of the stack.
+    type IterLifoMut<'a>: Iterator<Item = &'a mut Self::Item>
+    where
+        Self: 'a;
+
     /// Returns `true` if the stack is empty.
     fn s_is_empty(&self) -> bool;
 
+    /// Returns the number of items in the stack.
+    fn s_len(&self) -> usize;
+
     /// Pushes an item to the stack.
     ///
     /// For vector, use [`Vec::push`] instead.
     ///
+    /// [`Vec::push`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html#method.push
+    ///
+    /// ## Panics
+    ///
+    /// Fixed-capacity stacks, such as [`ArrayStack`], panic if the stack is full.
+    ///
     /// ## Also see
     ///
     /// * [`Stack::s_push_checked`]
@@ -70,21 +252,99 @@ pub trait Stack {
     ///
     /// ## Notes
     ///
-    /// For vector, use [`Vec::push`] instead. It is meant primarily for the `heapless::Vec`.
+    /// For vector, use [`Vec::push`] instead. It is meant primarily for fixed-capacity stacks,
+    /// such as [`ArrayStack`] or `heapless::Vec`, and returns `None` if the stack is full.
+    ///
+    /// [`Vec::push`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html#method.push
     ///
     /// ## Also see
     ///
     /// * [`Stack::s_push`]
+    /// * [`Stack::s_try_push`]
+    #[inline]
     fn s_push_checked(&mut self, item: Self::Item) -> Option<()> {
+        self.s_try_push(item).ok()
+    }
+
+    /// Pushes an item to the stack or hands it back in [`CapacityError`] if the stack is full.
+    ///
+    /// ## Notes
+    ///
+    /// Unlike [`Stack::s_push_checked`], the rejected item is not lost.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::s_push`]
+    /// * [`Stack::try_lifo_push`]
+    #[inline]
+    fn s_try_push(&mut self, item: Self::Item) -> Result<(), CapacityError<Self::Item>> {
         self.s_push(item);
-        Some(())
+        Ok(())
     }
 
     // we don't create chain push because Extend::extend_one API will be better
 
     /// Pushes an item to the stack and returns an the "entry" object
     /// corresponding to the pushed element.
-    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<Self>;
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self>;
+
+    /// Pushes the item returned by `f` to the stack and returns the "entry" object
+    /// corresponding to the pushed element.
+    ///
+    /// ## Notes
+    ///
+    /// By default, this is the same as `lifo_push(f())`. The implementors of
+    /// [`ContiguousStack`] call `f` after reserving the slot and write its result straight
+    /// into the slot, which gives the optimizer a chance to construct the item in place.
+    /// To guarantee this, use [`ContiguousStack::lifo_push_uninit`].
+    #[inline]
+    fn lifo_push_with<F: FnOnce() -> Self::Item>(&mut self, f: F) -> LIFOEntry<'_, Self> {
+        self.lifo_push(f())
+    }
+
+    /// Pushes an item to the stack and returns the "tentative entry" object corresponding
+    /// to the pushed element.
+    ///
+    /// The element is popped when the returned object is dropped, unless
+    /// [`TentativeEntry::commit`] is called.
+    ///
+    /// ## Notes
+    ///
+    /// This is useful when the element is initialized through the entry by fallible code,
+    /// so that a panic or an early return doesn't leave a half-initialized element on the stack.
+    #[inline]
+    fn lifo_push_tentative(&mut self, item: Self::Item) -> TentativeEntry<'_, Self> {
+        TentativeEntry::new(self.lifo_push(item))
+    }
+
+    /// Pushes an item to the stack and returns the "entry" object corresponding to the pushed
+    /// element or `None` if the stack is full.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::lifo_push`]
+    /// * [`Stack::try_lifo_push`]
+    #[inline]
+    fn lifo_push_checked(&mut self, item: Self::Item) -> Option<LIFOEntry<'_, Self>> {
+        self.try_lifo_push(item).ok()
+    }
+
+    /// Pushes an item to the stack and returns the "entry" object corresponding to the pushed
+    /// element or hands the item back in [`CapacityError`] if the stack is full.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::lifo_push`]
+    /// * [`Stack::s_try_push`]
+    #[inline]
+    fn try_lifo_push(
+        &mut self,
+        item: Self::Item,
+    ) -> Result<LIFOEntry<'_, Self>, CapacityError<Self::Item>> {
+        self.s_try_push(item)?;
+        // We just pushed to the stack, so the stack is not empty.
+        Ok(unsafe { self.lifo_unchecked() })
+    }
 
     /// Pops an item from the stack.
     ///
@@ -92,6 +352,8 @@ pub trait Stack {
     ///
     /// For vector, use [`Vec::pop`] instead.
     ///
+    /// [`Vec::pop`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html#method.pop
+    ///
     /// ## Also see
     ///
     /// * [`Stack::s_pop_unchecked`].
@@ -111,6 +373,128 @@ pub trait Stack {
         self.s_pop().unwrap_unchecked()
     }
 
+    /// Pushes all items of the iterator to the stack, so that the last item becomes the top.
+    ///
+    /// ## Panics
+    ///
+    /// Fixed-capacity stacks panic if the stack becomes full, as in [`Stack::s_push`].
+    ///
+    /// ## Notes
+    ///
+    /// For vector, use [`Vec::extend`] instead.
+    ///
+    /// [`Vec::extend`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html#method.extend
+    #[inline]
+    fn s_extend<I: IntoIterator<Item = Self::Item>>(&mut self, iter: I) {
+        for item in iter {
+            self.s_push(item);
+        }
+    }
+
+    /// Pops up to `n` items from the stack and drops them.
+    ///
+    /// Returns the number of popped items.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::s_truncate`]
+    /// * [`Stack::s_drain_top`]
+    #[inline]
+    fn s_pop_n(&mut self, n: usize) -> usize {
+        let len = self.s_len();
+        let new_len = len.saturating_sub(n);
+        self.s_truncate(new_len);
+        len - new_len
+    }
+
+    /// Pops items from the stack until there are at most `len` items left.
+    ///
+    /// ## Notes
+    ///
+    /// For vector, use [`Vec::truncate`] instead.
+    ///
+    /// [`Vec::truncate`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html#method.truncate
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::s_pop_n`]
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        for _ in len..self.s_len() {
+            // SAFETY: The stack has more than `len` items, so the call is safe.
+            drop(unsafe { self.s_pop_unchecked() });
+        }
+    }
+
+    /// Returns an iterator that pops up to `n` items from the stack, yielding them
+    /// in LIFO order.
+    ///
+    /// If the iterator is dropped before it is exhausted, the remaining items are popped
+    /// and dropped as well.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::s_pop_n`]
+    #[inline]
+    fn s_drain_top(&mut self, n: usize) -> DrainTop<'_, Self> {
+        let remaining = n.min(self.s_len());
+        DrainTop::new(self, remaining)
+    }
+
+    /// Returns an iterator that pops the items from the stack in LIFO order
+    /// until the stack is empty.
+    ///
+    /// Unlike [`Stack::s_drain_top`], the items that were not yielded stay on the stack
+    /// when the iterator is dropped.
+    #[inline]
+    fn pop_iter(&mut self) -> PopIter<'_, Self> {
+        PopIter::new(self)
+    }
+
+    /// Returns an iterator that pops the items from the stack in LIFO order
+    /// while they satisfy the predicate.
+    ///
+    /// The first item that does not satisfy the predicate is not popped and remains
+    /// reachable as a [`LIFOEntry`] via [`PopWhile::into_lifo`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use stack_trait::{ArrayStack, Stack};
+    ///
+    /// let mut stack: ArrayStack<char, 8> = ArrayStack::new();
+    /// stack.s_extend("(ab".chars());
+    /// let mut letters = stack.pop_while(|c| c.is_alphabetic());
+    /// assert_eq!(letters.by_ref().collect::<String>(), "ba");
+    /// assert_eq!(letters.into_lifo().unwrap().pop_pointee(), '(');
+    /// ```
+    #[inline]
+    fn pop_while<P: FnMut(&mut Self::Item) -> bool>(
+        &mut self,
+        predicate: P,
+    ) -> PopWhile<'_, Self, P> {
+        PopWhile::new(self, predicate)
+    }
+
+    /// Pops the top item from the stack if it satisfies the predicate.
+    ///
+    /// Returns `None` if the stack is empty or the predicate returned `false`.
+    ///
+    /// ## Notes
+    ///
+    /// For vector, use [`Vec::pop_if`] instead.
+    ///
+    /// [`Vec::pop_if`]: https://doc.rust-lang.org/alloc/vec/struct.Vec.html#method.pop_if
+    #[inline]
+    fn pop_if(&mut self, predicate: impl FnOnce(&mut Self::Item) -> bool) -> Option<Self::Item> {
+        let mut entry = self.lifo()?;
+        if predicate(&mut entry) {
+            Some(entry.pop_pointee())
+        } else {
+            None
+        }
+    }
+
     /// Returns an "entry" object corresponding to the top element of the stack.
     ///
     /// ## Notes
@@ -122,7 +506,7 @@ pub trait Stack {
     ///
     /// * [`Stack::lifo_unchecked`]
     #[inline]
-    fn lifo(&mut self) -> Option<LIFOEntry<Self>> {
+    fn lifo(&mut self) -> Option<LIFOEntry<'_, Self>> {
         if self.s_is_empty() {
             None
         } else {
@@ -130,6 +514,22 @@ pub trait Stack {
         }
     }
 
+    /// Returns a "top entry" object, which is either occupied by the top element of the stack
+    /// or vacant if the stack is empty.
+    ///
+    /// ## Notes
+    ///
+    /// This is useful when an element needs to be pushed to an empty stack
+    /// without re-borrowing the stack.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::lifo`]
+    #[inline]
+    fn top_entry(&mut self) -> TopEntry<'_, Self> {
+        TopEntry::new(self)
+    }
+
     /// Returns an "entry" object corresponding to the top element of the stack
     /// without checking if the stack is empty.
     ///
@@ -141,7 +541,7 @@ pub trait Stack {
     ///
     /// * [`Stack::lifo`]
     #[inline]
-    unsafe fn lifo_unchecked(&mut self) -> LIFOEntry<Self> {
+    unsafe fn lifo_unchecked(&mut self) -> LIFOEntry<'_, Self> {
         self.lifo().unwrap_unchecked()
     }
 
@@ -188,28 +588,229 @@ pub trait Stack {
     unsafe fn lifo_mut_unchecked(&mut self) -> &mut Self::Item {
         self.lifo_mut().unwrap_unchecked()
     }
+
+    /// Returns an iterator over shared references to the items, from the top
+    /// to the bottom of the stack.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::iter_lifo_mut`]
+    fn iter_lifo(&self) -> Self::IterLifo<'_>;
+
+    /// Returns an iterator over mutable references to the items, from the top
+    /// to the bottom of the stack.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::iter_lifo`]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_>;
+
+    /// Returns a shared reference to the element at depth `n`, where the top element
+    /// is at depth `0`.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::peek_nth_unchecked`]
+    /// * [`Stack::peek_nth_mut`]
+    /// * [`Stack::depth_entry`]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item>;
+
+    /// Returns a shared reference to the element at depth `n` without checking
+    /// if the stack is deep enough.
+    ///
+    /// ## Safety
+    ///
+    /// `n` must be less than [`Stack::s_len`].
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::peek_nth`]
+    #[inline]
+    unsafe fn peek_nth_unchecked(&self, n: usize) -> &Self::Item {
+        self.peek_nth(n).unwrap_unchecked()
+    }
+
+    /// Returns a mutable reference to the element at depth `n`, where the top element
+    /// is at depth `0`.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::peek_nth_mut_unchecked`]
+    /// * [`Stack::peek_nth`]
+    /// * [`Stack::depth_entry`]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item>;
+
+    /// Returns a mutable reference to the element at depth `n` without checking
+    /// if the stack is deep enough.
+    ///
+    /// ## Safety
+    ///
+    /// `n` must be less than [`Stack::s_len`].
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::peek_nth_mut`]
+    #[inline]
+    unsafe fn peek_nth_mut_unchecked(&mut self, n: usize) -> &mut Self::Item {
+        self.peek_nth_mut(n).unwrap_unchecked()
+    }
+
+    /// Removes the element at depth `n` from the stack, where the top element
+    /// is at depth `0`.
+    ///
+    /// The elements above it are shifted down by one.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::s_remove_nth_unchecked`]
+    /// * [`Stack::s_pop`]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item>;
+
+    /// Removes the element at depth `n` from the stack without checking
+    /// if the stack is deep enough.
+    ///
+    /// ## Safety
+    ///
+    /// `n` must be less than [`Stack::s_len`].
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::s_remove_nth`]
+    #[inline]
+    unsafe fn s_remove_nth_unchecked(&mut self, n: usize) -> Self::Item {
+        self.s_remove_nth(n).unwrap_unchecked()
+    }
+
+    /// Creates a transactional checkpoint of the stack.
+    ///
+    /// The changes made through the returned [`StackTxn`] are rolled back when it is
+    /// dropped or [rolled back](StackTxn::rollback) explicitly, unless they are
+    /// [committed](StackTxn::commit). Checkpoints can be nested.
+    ///
+    /// ## Notes
+    ///
+    /// This is useful for speculative pushes in backtracking parsers.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    fn checkpoint(&mut self) -> StackTxn<'_, Self>
+    where
+        Self::Item: Clone,
+    {
+        StackTxn::new(self)
+    }
+
+    /// Returns an "entry" object corresponding to the element at depth `n`,
+    /// where the top element is at depth `0`.
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::depth_entry_unchecked`]
+    /// * [`Stack::lifo`]
+    #[inline]
+    fn depth_entry(&mut self, n: usize) -> Option<DepthEntry<'_, Self>> {
+        if n < self.s_len() {
+            Some(unsafe { DepthEntry::new(self, n) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an "entry" object corresponding to the element at depth `n`
+    /// without checking if the stack is deep enough.
+    ///
+    /// ## Safety
+    ///
+    /// `n` must be less than [`Stack::s_len`].
+    ///
+    /// ## Also see
+    ///
+    /// * [`Stack::depth_entry`]
+    #[inline]
+    unsafe fn depth_entry_unchecked(&mut self, n: usize) -> DepthEntry<'_, Self> {
+        DepthEntry::new(self, n)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+unsafe impl<T> ContiguousStack for Vec<T> {
+    #[inline]
+    fn spare_top(&mut self) -> Option<&mut core::mem::MaybeUninit<Self::Item>> {
+        self.reserve(1);
+        self.spare_capacity_mut().first_mut()
+    }
+
+    #[inline]
+    unsafe fn assume_pushed(&mut self) {
+        self.set_len(self.len() + 1);
+    }
+}
+
+/// Converts the depth from the top of the stack into the index from the bottom.
+#[inline]
+pub(crate) fn index_from_depth(len: usize, n: usize) -> Option<usize> {
+    len.checked_sub(n)?.checked_sub(1)
+}
+
+/// Implementors of this trait are stacks with a fixed capacity.
+///
+/// ## Also see
+///
+/// * [`Stack::s_try_push`]
+/// * [`Stack::try_lifo_push`]
+pub trait BoundedStack: Stack {
+    /// Returns the number of items that can be pushed before the stack becomes full.
+    fn remaining_capacity(&self) -> usize;
+
+    /// Returns `true` if the stack is full.
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 impl<T> Stack for Vec<T> {
     type Item = T;
 
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, T>>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, T>>
+    where
+        Self: 'a;
+
     #[inline]
     fn s_is_empty(&self) -> bool {
         self.is_empty()
     }
 
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.len()
+    }
+
     #[inline]
     fn s_push(&mut self, item: Self::Item) {
         self.push(item);
     }
 
     #[inline]
-    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<Self> {
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
         self.push(item);
         // We just pushed to the vector, so the vector is not empty.
         unsafe { self.lifo_unchecked() }
     }
 
+    #[inline]
+    fn lifo_push_with<F: FnOnce() -> Self::Item>(&mut self, f: F) -> LIFOEntry<'_, Self> {
+        self.lifo_push_uninit().write(f())
+    }
+
     #[inline]
     fn s_pop(&mut self) -> Option<Self::Item> {
         self.pop()
@@ -224,13 +825,252 @@ impl<T> Stack for Vec<T> {
     fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
         self.last_mut()
     }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.iter().rev().nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.iter_mut().rev().nth(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = index_from_depth(self.len(), n)?;
+        Some(self.remove(index))
+    }
+
+    #[inline]
+    fn s_extend<I: IntoIterator<Item = Self::Item>>(&mut self, iter: I) {
+        self.extend(iter);
+    }
+
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.iter().rev()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.iter_mut().rev()
+    }
 }
 
-#[cfg(test)]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T> Stack for VecDeque<T> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = Rev<vec_deque::Iter<'a, T>>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = Rev<vec_deque::IterMut<'a, T>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.push_back(item);
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.push_back(item);
+        // We just pushed to the deque, so the deque is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.pop_back()
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.back()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.back_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.iter().rev().nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.iter_mut().rev().nth(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = index_from_depth(self.len(), n)?;
+        self.remove(index)
+    }
+
+    #[inline]
+    fn s_extend<I: IntoIterator<Item = Self::Item>>(&mut self, iter: I) {
+        self.extend(iter);
+    }
+
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.iter().rev()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.iter_mut().rev()
+    }
+}
+
+/// An adapter that treats the front of a double-ended container as the top of the stack.
+///
+/// The container itself implements [`Stack`] with the back as the top, so wrapping a mutable
+/// reference to it in [`FrontStack`] allows the same generic code to work against either end.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use stack_trait::{FrontStack, Stack};
+///
+/// let mut deque: VecDeque<i32> = VecDeque::from(vec![1, 2, 3]);
+/// let mut front = FrontStack::new(&mut deque);
+/// let entry = front.lifo_push(0);
+/// assert_eq!(entry.pop_pointee(), 0);
+/// assert_eq!(*front.lifo().unwrap(), 1);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct FrontStack<'a, C: ?Sized>(&'a mut C);
+
+#[cfg(feature = "alloc")]
+impl<'a, C: ?Sized> FrontStack<'a, C> {
+    /// Creates a new adapter from the mutable reference to the container.
+    pub fn new(container: &'a mut C) -> Self {
+        Self(container)
+    }
+
+    /// Returns the mutable reference to the underlying container.
+    pub fn into_inner(self) -> &'a mut C {
+        let FrontStack(container) = self;
+        container
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> Stack for FrontStack<'a, VecDeque<T>> {
+    type Item = T;
+
+    type IterLifo<'b>
+        = vec_deque::Iter<'b, T>
+    where
+        Self: 'b;
+    type IterLifoMut<'b>
+        = vec_deque::IterMut<'b, T>
+    where
+        Self: 'b;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.0.push_front(item);
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.0.push_front(item);
+        // We just pushed to the deque, so the deque is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.0.front()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.0.front_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.0.get(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.0.get_mut(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.remove(n)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.0.iter()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.0.iter_mut()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
+    // the entry is dropped explicitly to release the borrow of the stack
+    #[allow(clippy::drop_non_drop)]
     fn get_or_insert() {
         let mut stack = vec![1, 2, 3];
         let mut entry = stack.lifo_push(4);
@@ -243,4 +1083,125 @@ mod tests {
         assert_eq!(entry.pop_pointee(), 5);
         assert_eq!(stack, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn entry_conversions() {
+        fn top_of(stack: &mut Vec<i32>) -> &mut i32 {
+            stack.lifo_push(0).into_mut()
+        }
+
+        let mut stack = vec![1, 2];
+        *top_of(&mut stack) = 3;
+        let mut entry = stack.lifo().unwrap();
+        assert_eq!(entry.reborrow().pop_pointee(), 3);
+        assert_eq!(entry.as_stack(), &vec![1, 2]);
+        entry.into_stack().push(4);
+        assert_eq!(stack, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn entry_rewrites() {
+        let mut stack = vec![vec![1], vec![2]];
+        let mut other = vec![vec![3]];
+        let mut entry = stack.lifo().unwrap();
+        assert_eq!(entry.replace(vec![4]), vec![2]);
+        entry.swap_with(&mut other.lifo().unwrap());
+        assert_eq!(entry.take(), vec![3]);
+        let entry = entry.map_in_place(|mut v| {
+            v.push(5);
+            v
+        });
+        assert_eq!(*entry, vec![5]);
+        assert_eq!(other, vec![vec![4]]);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            stack
+                .lifo()
+                .unwrap()
+                .map_in_place(|_| panic!("rewrite failed"));
+        }));
+        assert!(result.is_err());
+        assert_eq!(stack, vec![vec![1]]);
+    }
+
+    #[test]
+    fn depth_entries() {
+        let mut stack = vec![1, 2, 3];
+        assert_eq!(stack.peek_nth(0), Some(&3));
+        assert_eq!(stack.peek_nth(2), Some(&1));
+        assert_eq!(stack.peek_nth(3), None);
+        *stack.peek_nth_mut(1).unwrap() = 20;
+        assert!(stack.depth_entry(3).is_none());
+        let mut entry = stack.depth_entry(1).unwrap();
+        assert_eq!(*entry, 20);
+        *entry += 1;
+        assert_eq!(entry.remove_pointee(), 21);
+        assert_eq!(stack, vec![1, 3]);
+        assert_eq!(stack.s_len(), 2);
+    }
+
+    #[test]
+    fn bulk_operations() {
+        let mut stack = vec![1];
+        stack.s_extend(2..=6);
+        assert_eq!(stack.s_pop_n(2), 2);
+        assert_eq!(stack.s_drain_top(3).collect::<Vec<_>>(), vec![4, 3, 2]);
+        stack.s_extend([7, 8]);
+        assert_eq!(stack.s_drain_top(10).next(), Some(8));
+        assert!(stack.is_empty());
+        assert_eq!(stack.s_pop_n(1), 0);
+    }
+
+    #[test]
+    fn iterate_from_top() {
+        fn describe<S: Stack<Item = i32>>(stack: &mut S) -> Vec<i32> {
+            stack.iter_lifo_mut().for_each(|item| *item *= 10);
+            stack.iter_lifo().copied().collect()
+        }
+
+        let mut deque: VecDeque<i32> = VecDeque::from(vec![1, 2, 3]);
+        assert_eq!(describe(&mut vec![1, 2, 3]), vec![30, 20, 10]);
+        assert_eq!(describe(&mut FrontStack::new(&mut deque)), vec![10, 20, 30]);
+        assert_eq!(describe(&mut deque), vec![300, 200, 100]);
+    }
+
+    #[test]
+    fn in_place_construction() {
+        let mut stack: Vec<[u64; 32]> = Vec::new();
+        assert_eq!(stack.lifo_push_with(|| [1; 32])[0], 1);
+        let mut full: ArrayStack<u8, 1> = ArrayStack::new();
+        full.s_push(1);
+        let mut called = false;
+        let pushed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            full.lifo_push_with(|| {
+                called = true;
+                2
+            });
+        }));
+        // The full stack panics while reserving the slot, before `f` is called.
+        assert!(pushed.is_err() && !called);
+        let mut entry = stack.lifo_push_uninit();
+        entry.as_uninit().write([2; 32]);
+        let entry = unsafe { entry.assume_init() };
+        assert_eq!(entry[31], 2);
+        assert_eq!(
+            stack.lifo_push_uninit().write([3; 32]).pop_pointee(),
+            [3; 32]
+        );
+        assert_eq!(stack.len(), 2);
+    }
+
+    #[test]
+    fn vec_deque_both_ends() {
+        fn bump_top<S: Stack<Item = i32> + ?Sized>(stack: &mut S) -> i32 {
+            let mut entry = stack.lifo().unwrap();
+            *entry += 10;
+            entry.pop_pointee()
+        }
+
+        let mut deque: VecDeque<i32> = VecDeque::from(vec![1, 2, 3]);
+        assert_eq!(bump_top(&mut deque), 13);
+        assert_eq!(bump_top(&mut FrontStack::new(&mut deque)), 11);
+        assert_eq!(deque, VecDeque::from(vec![2]));
+    }
 }
@@ -0,0 +1,362 @@
+use core::ops::{Deref, DerefMut};
+use std::sync::{
+    LockResult, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+use crate::{ConcurrentStack, Stack};
+
+/// An "entry" object corresponding to the top element of a locked stack.
+///
+/// It holds the lock guard, so the stack stays locked while the entry exists.
+/// Existence of this object guarantees that the stack is not empty.
+///
+/// ## Also see
+///
+/// * [`LIFOEntry`](crate::LIFOEntry)
+pub struct LockedLIFOEntry<G>(G);
+
+impl<G: DerefMut> LockedLIFOEntry<G>
+where
+    G::Target: Stack,
+{
+    /// Creates a new "entry" object from the lock guard of the stack.
+    ///
+    /// ## Safety
+    ///
+    /// The stack must not be empty.
+    pub unsafe fn new(guard: G) -> Self {
+        Self(guard)
+    }
+
+    /// Pops the LIFO element from the stack and releases the lock.
+    pub fn pop_pointee(self) -> <G::Target as Stack>::Item {
+        let LockedLIFOEntry(mut guard) = self;
+        // SAFETY: The stack is not empty by the virtue of
+        // existence of the LockedLIFOEntry object, so the call is safe.
+        unsafe { guard.s_pop_unchecked() }
+    }
+
+    /// Converts the entry into the lock guard of the stack.
+    pub fn into_guard(self) -> G {
+        let LockedLIFOEntry(guard) = self;
+        guard
+    }
+}
+
+impl<G: DerefMut> Deref for LockedLIFOEntry<G>
+where
+    G::Target: Stack,
+{
+    type Target = <G::Target as Stack>::Item;
+
+    fn deref(&self) -> &Self::Target {
+        let LockedLIFOEntry(guard) = self;
+        // SAFETY: The stack is not empty, so the call is safe.
+        unsafe { guard.lifo_ref_unchecked() }
+    }
+}
+
+impl<G: DerefMut> DerefMut for LockedLIFOEntry<G>
+where
+    G::Target: Stack,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let LockedLIFOEntry(guard) = self;
+        // SAFETY: The stack is not empty, so the call is safe.
+        unsafe { guard.lifo_mut_unchecked() }
+    }
+}
+
+/// A shared reference to the top element of a read-locked stack.
+///
+/// It holds the read guard, so the stack stays locked for writing while it exists.
+pub struct LockedLIFORef<G>(G);
+
+impl<G: Deref> LockedLIFORef<G>
+where
+    G::Target: Stack,
+{
+    /// Creates a new reference from the read guard of the stack.
+    ///
+    /// ## Safety
+    ///
+    /// The stack must not be empty.
+    pub unsafe fn new(guard: G) -> Self {
+        Self(guard)
+    }
+
+    /// Converts the reference into the read guard of the stack.
+    pub fn into_guard(self) -> G {
+        let LockedLIFORef(guard) = self;
+        guard
+    }
+}
+
+impl<G: Deref> Deref for LockedLIFORef<G>
+where
+    G::Target: Stack,
+{
+    type Target = <G::Target as Stack>::Item;
+
+    fn deref(&self) -> &Self::Target {
+        let LockedLIFORef(guard) = self;
+        // SAFETY: The stack is not empty, so the call is safe.
+        unsafe { guard.lifo_ref_unchecked() }
+    }
+}
+
+/// A stack protected by a [`Mutex`] that keeps the entry API for the top element.
+///
+/// ## Notes
+///
+/// Like [`Mutex`], the stack is poisoned if a thread panics while holding the lock,
+/// for example, in the middle of mutating the top element through a [`LockedLIFOEntry`].
+/// The locking methods then return [`PoisonError`], which still gives access to the stack
+/// with [`PoisonError::into_inner`] if the caller knows how to recover. The methods of
+/// [`ConcurrentStack`] panic on a poisoned lock.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::LockedStack;
+///
+/// let stack = LockedStack::new(vec![1, 2]);
+/// let mut entry = stack.lock_lifo().unwrap().unwrap();
+/// *entry += 1;
+/// // Popping releases the lock.
+/// assert_eq!(entry.pop_pointee(), 3);
+/// assert_eq!(*stack.lock().unwrap(), vec![1]);
+/// ```
+#[derive(Debug, Default)]
+pub struct LockedStack<C>(Mutex<C>);
+
+impl<C: Stack> LockedStack<C> {
+    /// Creates a new locked stack.
+    pub fn new(stack: C) -> Self {
+        Self(Mutex::new(stack))
+    }
+
+    /// Locks the stack and returns the guard.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the guard if the lock is poisoned.
+    pub fn lock(&self) -> LockResult<MutexGuard<'_, C>> {
+        self.0.lock()
+    }
+
+    /// Locks the stack and returns the "entry" object corresponding to the top element
+    /// or `None` if the stack is empty.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the result if the lock is poisoned.
+    pub fn lock_lifo(&self) -> LockResult<Option<LockedLIFOEntry<MutexGuard<'_, C>>>> {
+        map_lock_result(self.lock(), lifo_entry)
+    }
+
+    /// Returns the mutable reference to the stack without locking.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the reference if the lock is poisoned.
+    pub fn get_mut(&mut self) -> LockResult<&mut C> {
+        self.0.get_mut()
+    }
+
+    /// Returns the stack.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the stack if the lock is poisoned.
+    pub fn into_inner(self) -> LockResult<C> {
+        self.0.into_inner()
+    }
+}
+
+impl<C: Stack> ConcurrentStack for LockedStack<C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn push(&self, item: Self::Item) {
+        self.lock().expect(POISONED).s_push(item);
+    }
+
+    #[inline]
+    fn pop(&self) -> Option<Self::Item> {
+        self.lock().expect(POISONED).s_pop()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.lock().expect(POISONED).s_is_empty()
+    }
+}
+
+/// A stack protected by a [`RwLock`] that keeps the entry API for the top element.
+///
+/// ## Notes
+///
+/// Like [`RwLock`], the stack is poisoned if a thread panics while holding the write lock.
+/// The locking methods then return [`PoisonError`], and the methods of [`ConcurrentStack`]
+/// panic. See [`LockedStack`] for details.
+#[derive(Debug, Default)]
+pub struct RwLockedStack<C>(RwLock<C>);
+
+impl<C: Stack> RwLockedStack<C> {
+    /// Creates a new locked stack.
+    pub fn new(stack: C) -> Self {
+        Self(RwLock::new(stack))
+    }
+
+    /// Locks the stack for reading and returns the guard.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the guard if the lock is poisoned.
+    pub fn read(&self) -> LockResult<RwLockReadGuard<'_, C>> {
+        self.0.read()
+    }
+
+    /// Locks the stack for writing and returns the guard.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the guard if the lock is poisoned.
+    pub fn write(&self) -> LockResult<RwLockWriteGuard<'_, C>> {
+        self.0.write()
+    }
+
+    /// Locks the stack for reading and returns the reference to the top element
+    /// or `None` if the stack is empty.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the result if the lock is poisoned.
+    pub fn read_lifo(&self) -> LockResult<Option<LockedLIFORef<RwLockReadGuard<'_, C>>>> {
+        map_lock_result(self.read(), |guard| {
+            if guard.s_is_empty() {
+                None
+            } else {
+                // SAFETY: The stack is not empty, so the call is safe.
+                Some(unsafe { LockedLIFORef::new(guard) })
+            }
+        })
+    }
+
+    /// Locks the stack for writing and returns the "entry" object corresponding to the top
+    /// element or `None` if the stack is empty.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the result if the lock is poisoned.
+    pub fn write_lifo(&self) -> LockResult<Option<LockedLIFOEntry<RwLockWriteGuard<'_, C>>>> {
+        map_lock_result(self.write(), lifo_entry)
+    }
+
+    /// Returns the mutable reference to the stack without locking.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the reference if the lock is poisoned.
+    pub fn get_mut(&mut self) -> LockResult<&mut C> {
+        self.0.get_mut()
+    }
+
+    /// Returns the stack.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`PoisonError`] with the stack if the lock is poisoned.
+    pub fn into_inner(self) -> LockResult<C> {
+        self.0.into_inner()
+    }
+}
+
+impl<C: Stack> ConcurrentStack for RwLockedStack<C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn push(&self, item: Self::Item) {
+        self.write().expect(POISONED).s_push(item);
+    }
+
+    #[inline]
+    fn pop(&self) -> Option<Self::Item> {
+        self.write().expect(POISONED).s_pop()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.read().expect(POISONED).s_is_empty()
+    }
+}
+
+const POISONED: &str = "the lock of the stack is poisoned";
+
+/// Applies `f` to the guard of a lock, keeping the poisoned state.
+fn map_lock_result<G, T>(result: LockResult<G>, f: impl FnOnce(G) -> T) -> LockResult<T> {
+    match result {
+        Ok(guard) => Ok(f(guard)),
+        Err(err) => Err(PoisonError::new(f(err.into_inner()))),
+    }
+}
+
+fn lifo_entry<G: DerefMut>(guard: G) -> Option<LockedLIFOEntry<G>>
+where
+    G::Target: Stack,
+{
+    if guard.s_is_empty() {
+        None
+    } else {
+        // SAFETY: The stack is not empty, so the call is safe.
+        Some(unsafe { LockedLIFOEntry::new(guard) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn shared_entries() {
+        let stack = Arc::new(RwLockedStack::new(Vec::new()));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        stack.push(1);
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .for_each(|handle| handle.join().unwrap());
+        *stack.write_lifo().unwrap().unwrap() += 1;
+        assert_eq!(*stack.read_lifo().unwrap().unwrap(), 2);
+        assert_eq!(stack.read().unwrap().len(), 400);
+
+        let stack = LockedStack::new(vec![1]);
+        assert_eq!(stack.lock_lifo().unwrap().unwrap().pop_pointee(), 1);
+        assert!(stack.lock_lifo().unwrap().is_none());
+    }
+
+    #[test]
+    fn poisoned_by_half_written_entry() {
+        let stack = LockedStack::new(vec![vec![1]]);
+        let result = std::panic::catch_unwind(|| {
+            let mut entry = stack.lock_lifo().unwrap().unwrap();
+            entry.push(2);
+            panic!("interrupted before the item was complete");
+        });
+        assert!(result.is_err());
+        let err = stack.lock_lifo().err().unwrap();
+        assert_eq!(*err.into_inner().unwrap(), vec![1, 2]);
+        assert!(std::panic::catch_unwind(|| stack.pop()).is_err());
+        assert!(stack.into_inner().is_err());
+    }
+}
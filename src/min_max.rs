@@ -0,0 +1,262 @@
+use alloc::vec::Vec;
+use core::{fmt, iter::Rev, slice};
+
+use crate::{
+    prefix::{PrefixStack, Step, Summary},
+    LIFOEntry, Stack,
+};
+
+/// A stack that tracks the minimum and maximum of its items, so that
+/// [`MinMaxStack::min`] and [`MinMaxStack::max`] are O(1).
+///
+/// The positions of the extrema of every prefix of the stack are stored alongside the
+/// items, so popping restores the extrema of the remaining items without a rescan.
+///
+/// ## Notes
+///
+/// Mutable references handed out by [`Stack::lifo_mut`], [`Stack::peek_nth_mut`] and
+/// [`Stack::iter_lifo_mut`] invalidate the extrema of the prefixes that include the
+/// referenced items. The next push, pop or removal recomputes them, and so does the next
+/// query, which saves the result, so only the first query after the mutation is O(n).
+/// Mutations of the top element through [`LIFOEntry`] invalidate only the topmost prefix.
+///
+/// The extrema are saved by the queries through a [`RefCell`](core::cell::RefCell),
+/// so the stack is not [`Sync`].
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{MinMaxStack, Stack};
+///
+/// let mut stack = MinMaxStack::new();
+/// stack.s_extend([3, 1, 4]);
+/// assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&4)));
+///
+/// *stack.lifo().unwrap() = 0;
+/// assert_eq!((stack.min(), stack.max()), (Some(&0), Some(&3)));
+///
+/// stack.s_pop();
+/// assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&3)));
+/// ```
+#[derive(Clone)]
+pub struct MinMaxStack<T>(PrefixStack<T, Extrema>);
+
+// The indices of the minimum and the maximum of a prefix.
+struct Extrema;
+
+impl Summary for Extrema {
+    type Entry = (usize, usize);
+}
+
+impl<T: Ord> Step<T> for Extrema {
+    fn step(below: Option<&(usize, usize)>, items: &[T], index: usize) -> (usize, usize) {
+        let Some(&(min, max)) = below else {
+            return (index, index);
+        };
+        let item = &items[index];
+        (
+            if *item < items[min] { index } else { min },
+            if *item > items[max] { index } else { max },
+        )
+    }
+}
+
+impl<T> MinMaxStack<T> {
+    /// Creates a new empty stack.
+    pub const fn new() -> Self {
+        Self(PrefixStack::new())
+    }
+
+    /// Returns the number of items in the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.as_slice().len()
+    }
+
+    /// Returns `true` if the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.as_slice().is_empty()
+    }
+
+    /// Returns the items of the stack, from the bottom to the top.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.0.as_slice()
+    }
+
+    /// Returns the items of the stack, from the bottom to the top.
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.0.into_vec()
+    }
+}
+
+impl<T: Ord> MinMaxStack<T> {
+    /// Returns the minimum of the items or `None` if the stack is empty.
+    ///
+    /// If several items are equal to the minimum, the bottommost one is returned.
+    #[inline]
+    pub fn min(&self) -> Option<&T> {
+        self.0.summary().map(|(min, _)| &self.as_slice()[min])
+    }
+
+    /// Returns the maximum of the items or `None` if the stack is empty.
+    ///
+    /// If several items are equal to the maximum, the bottommost one is returned.
+    #[inline]
+    pub fn max(&self) -> Option<&T> {
+        self.0.summary().map(|(_, max)| &self.as_slice()[max])
+    }
+}
+
+impl<T> Default for MinMaxStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MinMaxStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for MinMaxStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for MinMaxStack<T> {}
+
+/// Collects the items from the bottom to the top of the stack.
+impl<T: Ord> FromIterator<T> for MinMaxStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::new();
+        stack.s_extend(iter);
+        stack
+    }
+}
+
+impl<T: Ord> Stack for MinMaxStack<T> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, T>>
+    where
+        T: 'a;
+
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, T>>
+    where
+        T: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.0.s_is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.0.s_len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.0.s_push(item);
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // SAFETY: We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.0.s_pop()
+    }
+
+    #[inline]
+    fn s_extend<I: IntoIterator<Item = Self::Item>>(&mut self, iter: I) {
+        self.0.s_extend(iter);
+    }
+
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        self.0.s_truncate(len);
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.0.lifo_ref()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.0.lifo_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.0.peek_nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.0.peek_nth_mut(n)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.s_remove_nth(n)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.0.iter_lifo()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.0.iter_lifo_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn extrema_follow_mutations() {
+        let mut stack: MinMaxStack<i32> = [5, 2, 8, 2].into_iter().collect();
+        assert_eq!((stack.min(), stack.max()), (Some(&2), Some(&8)));
+
+        let mut entry = stack.lifo().unwrap();
+        *entry = 9;
+        *entry -= 10;
+        assert_eq!((stack.min(), stack.max()), (Some(&-1), Some(&8)));
+
+        *stack.peek_nth_mut(1).unwrap() = 3;
+        assert_eq!((stack.min(), stack.max()), (Some(&-1), Some(&5)));
+        stack.iter_lifo_mut().for_each(|item| *item *= -1);
+        assert_eq!((stack.min(), stack.max()), (Some(&-5), Some(&1)));
+
+        assert_eq!(stack.s_remove_nth(0), Some(1));
+        assert_eq!((stack.min(), stack.max()), (Some(&-5), Some(&-2)));
+        assert_eq!(stack.lifo().unwrap().into_mut(), &mut -3);
+        stack.s_truncate(1);
+        assert_eq!((stack.min(), stack.max()), (Some(&-5), Some(&-5)));
+
+        stack.s_extend([7, 3, 9]);
+        stack.iter_lifo_mut().for_each(|item| *item += 1);
+        assert_eq!(stack.s_pop(), Some(10));
+        assert_eq!((stack.min(), stack.max()), (Some(&-4), Some(&8)));
+        stack.s_truncate(0);
+        assert_eq!((stack.min(), stack.max()), (None, None));
+        assert_eq!(stack.into_vec(), vec![]);
+    }
+}
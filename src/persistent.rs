@@ -0,0 +1,365 @@
+use alloc::sync::Arc;
+use core::{fmt, iter::FusedIterator};
+
+use crate::{LIFOEntry, Stack};
+
+#[derive(Clone)]
+struct Node<T> {
+    item: T,
+    next: Option<Arc<Node<T>>>,
+}
+
+/// An immutable stack with structural sharing, implemented as an [`Arc`]-linked list.
+///
+/// [`PersistentStack::push`] and [`PersistentStack::pop`] return new versions of the stack
+/// in O(1) and leave the original intact. Cloning is O(1) as well, so taking a snapshot
+/// is cheap.
+///
+/// A [`PersistentStack`] value is also a mutable handle to the current version, and
+/// [`Stack`] is implemented for it, so the existing code working with [`LIFOEntry`] keeps
+/// working. Mutations through [`Stack`] copy only the nodes shared with other versions
+/// that need to change.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{PersistentStack, Stack};
+///
+/// let empty = PersistentStack::new();
+/// let one = empty.push(1);
+/// let two = one.push(2);
+/// assert_eq!(two.pop().unwrap(), one);
+///
+/// // Mutating a handle doesn't affect the snapshots.
+/// let mut handle = two.clone();
+/// *handle.lifo().unwrap() = 20;
+/// handle.s_push(3);
+/// assert_eq!(handle.iter().copied().collect::<Vec<_>>(), vec![3, 20, 1]);
+/// assert_eq!(two.iter().copied().collect::<Vec<_>>(), vec![2, 1]);
+/// ```
+pub struct PersistentStack<T> {
+    head: Option<Arc<Node<T>>>,
+    len: usize,
+}
+
+impl<T> PersistentStack<T> {
+    /// Creates a new empty stack.
+    pub const fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    /// Returns the number of items in the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns a new version of the stack with the item pushed to the top.
+    pub fn push(&self, item: T) -> Self {
+        Self {
+            head: Some(Arc::new(Node {
+                item,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns a new version of the stack without the top item or `None` if the stack is empty.
+    pub fn pop(&self) -> Option<Self> {
+        let node = self.head.as_ref()?;
+        Some(Self {
+            head: node.next.clone(),
+            len: self.len - 1,
+        })
+    }
+
+    /// Returns a shared reference to the top item of the stack.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_deref().map(|node| &node.item)
+    }
+
+    /// Returns an iterator over the items, from the top to the bottom of the stack.
+    #[inline]
+    pub fn iter(&self) -> PersistentIter<'_, T> {
+        PersistentIter {
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+
+    /// Returns `true` if both stacks are the same version, i.e. share the top node.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(lhs), Some(rhs)) => Arc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for PersistentStack<T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for PersistentStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for PersistentStack<T> {
+    fn drop(&mut self) {
+        // The nodes are dropped iteratively, so that dropping a long list
+        // doesn't overflow the call stack.
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            match Arc::try_unwrap(node) {
+                Ok(mut node) => head = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: Eq> Eq for PersistentStack<T> {}
+
+/// Creates a stack from the items, from the bottom to the top.
+impl<T> FromIterator<T> for PersistentStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::new();
+        for item in iter {
+            stack.head = Some(Arc::new(Node {
+                item,
+                next: stack.head.take(),
+            }));
+            stack.len += 1;
+        }
+        stack
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentStack<T> {
+    type Item = &'a T;
+    type IntoIter = PersistentIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the items of a [`PersistentStack`], from the top to the bottom.
+pub struct PersistentIter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for PersistentIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.len -= 1;
+        Some(&node.item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for PersistentIter<'_, T> {}
+
+impl<T> FusedIterator for PersistentIter<'_, T> {}
+
+impl<T> Clone for PersistentIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            len: self.len,
+        }
+    }
+}
+
+/// An iterator over mutable references to the items of a [`PersistentStack`],
+/// from the top to the bottom.
+///
+/// The nodes shared with other versions are copied as the iterator advances.
+pub struct PersistentIterMut<'a, T> {
+    next: Option<&'a mut Arc<Node<T>>>,
+    len: usize,
+}
+
+impl<'a, T: Clone> Iterator for PersistentIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = Arc::make_mut(self.next.take()?);
+        self.next = node.next.as_mut();
+        self.len -= 1;
+        Some(&mut node.item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for PersistentIterMut<'_, T> {}
+
+impl<T: Clone> FusedIterator for PersistentIterMut<'_, T> {}
+
+impl<T: Clone> PersistentStack<T> {
+    /// Returns the mutable reference to the link pointing to the node at depth `n`,
+    /// copying the shared nodes above it.
+    ///
+    /// `n` must be less than the number of items.
+    fn link_mut(&mut self, n: usize) -> &mut Option<Arc<Node<T>>> {
+        let mut link = &mut self.head;
+        for _ in 0..n {
+            let node = link.as_mut().expect("the stack is deeper than `n`");
+            link = &mut Arc::make_mut(node).next;
+        }
+        link
+    }
+}
+
+impl<T: Clone> Stack for PersistentStack<T> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = PersistentIter<'a, T>
+    where
+        Self: 'a;
+    type IterLifoMut<'a>
+        = PersistentIterMut<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        *self = self.push(item);
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.s_remove_nth(0)
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.peek()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peek_nth_mut(0)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.iter()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        PersistentIterMut {
+            next: self.head.as_mut(),
+            len: self.len,
+        }
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.iter().nth(n)
+    }
+
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        if n >= self.len {
+            return None;
+        }
+        let node = self.link_mut(n).as_mut()?;
+        Some(&mut Arc::make_mut(node).item)
+    }
+
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            return None;
+        }
+        let link = self.link_mut(n);
+        let node = link.take()?;
+        let Node { item, next } = Arc::try_unwrap(node).unwrap_or_else(|node| (*node).clone());
+        *link = next;
+        self.len -= 1;
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::PersistentStack;
+    use crate::Stack;
+
+    #[test]
+    fn versions_are_independent() {
+        let base: PersistentStack<i32> = (1..=4).collect();
+        let mut handle = base.clone();
+        assert_eq!(handle.s_remove_nth(2), Some(2));
+        *handle.peek_nth_mut(1).unwrap() = 30;
+        handle.iter_lifo_mut().for_each(|item| *item += 100);
+        assert_eq!(handle.s_pop(), Some(104));
+        assert_eq!(handle.iter().copied().collect::<Vec<_>>(), vec![130, 101]);
+        assert_eq!(base.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+
+        let long: PersistentStack<i32> = (0..1_000_000).collect();
+        drop(long);
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::vec::Vec;
+use core::{cell::RefCell, iter::Rev, marker::PhantomData, slice};
+
+use crate::{index_from_depth, LIFOEntry, Stack};
+
+/// The kind of value that [`PrefixStack`] keeps for every prefix of its items.
+pub(crate) trait Summary {
+    /// The value kept for a prefix.
+    type Entry;
+}
+
+/// The way [`PrefixStack`] computes the [`Summary`] of a prefix of items of type `T`.
+pub(crate) trait Step<T>: Summary {
+    /// Returns the entry of `items[..=index]`, given the entry of `items[..index]`,
+    /// which is `None` for the bottom item.
+    fn step(below: Option<&Self::Entry>, items: &[T], index: usize) -> Self::Entry;
+}
+
+/// A stack that keeps the [`Summary`] of every prefix of its items, so that the summary
+/// of the whole stack is O(1). It backs [`MinMaxStack`](crate::MinMaxStack) and
+/// [`AggStack`](crate::AggStack).
+///
+/// Mutable references to the items invalidate the entries of the prefixes that include
+/// the referenced items. The next push, pop or removal recomputes them, and so does the
+/// next query, which saves the entries through the [`RefCell`], so that only the first
+/// query after the mutation is O(n).
+pub(crate) struct PrefixStack<T, S: Summary> {
+    items: Vec<T>,
+    // The entry of `items[..=i]`, for every `i` below the first item that may have been
+    // mutated since the last refresh.
+    prefix: RefCell<Vec<S::Entry>>,
+    summary: PhantomData<fn() -> S>,
+}
+
+impl<T, S: Summary> PrefixStack<T, S> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            prefix: RefCell::new(Vec::new()),
+            summary: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    #[inline]
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Marks the entries of the prefixes that include the item at `index` as stale.
+    #[inline]
+    fn invalidate_from(&mut self, index: usize) {
+        self.prefix.get_mut().truncate(index);
+    }
+}
+
+impl<T, S: Step<T>> PrefixStack<T, S> {
+    /// Returns the entry of the whole stack or `None` if the stack is empty.
+    pub(crate) fn summary(&self) -> Option<S::Entry>
+    where
+        S::Entry: Clone,
+    {
+        self.refresh();
+        self.prefix.borrow().last().cloned()
+    }
+
+    fn refresh(&self) {
+        let mut prefix = self.prefix.borrow_mut();
+        for index in prefix.len()..self.items.len() {
+            let entry = S::step(prefix.last(), &self.items, index);
+            prefix.push(entry);
+        }
+    }
+}
+
+impl<T: Clone, S: Summary> Clone for PrefixStack<T, S>
+where
+    S::Entry: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            prefix: self.prefix.clone(),
+            summary: PhantomData,
+        }
+    }
+}
+
+impl<T, S: Step<T>> Stack for PrefixStack<T, S> {
+    type Item = T;
+
+    type IterLifo<'a>
+        = Rev<slice::Iter<'a, T>>
+    where
+        Self: 'a;
+
+    type IterLifoMut<'a>
+        = Rev<slice::IterMut<'a, T>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.items.push(item);
+        self.refresh();
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.s_push(item);
+        // SAFETY: We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        let item = self.items.pop()?;
+        self.invalidate_from(self.items.len());
+        self.refresh();
+        Some(item)
+    }
+
+    #[inline]
+    fn s_extend<I: IntoIterator<Item = Self::Item>>(&mut self, iter: I) {
+        self.items.extend(iter);
+        self.refresh();
+    }
+
+    #[inline]
+    fn s_truncate(&mut self, len: usize) {
+        self.items.truncate(len);
+        self.invalidate_from(len);
+        self.refresh();
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.items.last()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.invalidate_from(self.items.len().checked_sub(1)?);
+        self.items.last_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.items.get(index_from_depth(self.items.len(), n)?)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        let index = index_from_depth(self.items.len(), n)?;
+        self.invalidate_from(index);
+        self.items.get_mut(index)
+    }
+
+    #[inline]
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = index_from_depth(self.items.len(), n)?;
+        let item = self.items.remove(index);
+        self.invalidate_from(index);
+        self.refresh();
+        Some(item)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.items.iter().rev()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.invalidate_from(0);
+        self.items.iter_mut().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static STEPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Sum;
+
+    impl Summary for Sum {
+        type Entry = u64;
+    }
+
+    impl Step<u64> for Sum {
+        fn step(below: Option<&u64>, items: &[u64], index: usize) -> u64 {
+            STEPS.fetch_add(1, Ordering::Relaxed);
+            below.copied().unwrap_or(0) + items[index]
+        }
+    }
+
+    #[test]
+    fn recomputed_once_after_mutation() {
+        let mut stack = PrefixStack::<u64, Sum>::new();
+        stack.s_extend(1..=100);
+        stack.iter_lifo_mut().for_each(|item| *item *= 2);
+        STEPS.store(0, Ordering::Relaxed);
+        for _ in 0..10 {
+            assert_eq!(stack.summary(), Some(10100));
+        }
+        assert_eq!(STEPS.load(Ordering::Relaxed), 100);
+
+        *stack.peek_nth_mut(1).unwrap() = 0;
+        assert_eq!(stack.s_pop(), Some(200));
+        assert_eq!(stack.prefix.borrow().len(), 99);
+        assert_eq!(stack.summary(), Some(9702));
+        assert_eq!(STEPS.load(Ordering::Relaxed), 101);
+    }
+}
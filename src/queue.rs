@@ -0,0 +1,230 @@
+use crate::Stack;
+#[cfg(feature = "alloc")]
+use crate::{AggStack, Dual, Monoid};
+
+/// A FIFO queue built from two stacks, with amortized O(1) [`TwoStackQueue::pop_front`].
+///
+/// The items are pushed to the back stack `S`. When the front stack `F` runs out of items,
+/// the back stack is drained into it, which reverses the order of the items, so the top of
+/// the front stack is the oldest item.
+///
+#[cfg_attr(
+    feature = "alloc",
+    doc = "With [`AggStack`] halves, the queue maintains the aggregate of the items in O(1),",
+    doc = "which is the classic sliding-window aggregation. See [`AggQueue`]."
+)]
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{ArrayStack, TwoStackQueue};
+///
+/// let mut queue = TwoStackQueue::<ArrayStack<i32, 4>>::new();
+/// queue.push_back(1);
+/// queue.push_back(2);
+/// assert_eq!(queue.pop_front(), Some(1));
+/// queue.push_back(3);
+/// assert_eq!(queue.front(), Some(&2));
+/// assert_eq!(queue.back(), Some(&3));
+/// assert_eq!(queue.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TwoStackQueue<S, F = S> {
+    front: F,
+    back: S,
+}
+
+/// A [`TwoStackQueue`] that maintains the aggregate of its items with the [`Monoid`] `M`.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{AggQueue, Monoid};
+///
+/// struct Max;
+///
+/// impl Monoid<u32> for Max {
+///     type Agg = u32;
+///
+///     fn identity() -> u32 {
+///         0
+///     }
+///
+///     fn lift(item: &u32) -> u32 {
+///         *item
+///     }
+///
+///     fn combine(a: &u32, b: &u32) -> u32 {
+///         *a.max(b)
+///     }
+/// }
+///
+/// let mut window = AggQueue::<u32, Max>::new();
+/// let mut maxima = Vec::new();
+/// for value in [3, 1, 4, 1, 5, 9, 2, 6] {
+///     window.push_back(value);
+///     if window.len() > 3 {
+///         window.pop_front();
+///     }
+///     maxima.push(window.aggregate());
+/// }
+/// assert_eq!(maxima, [3, 3, 4, 4, 5, 9, 9, 9]);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type AggQueue<T, M> = TwoStackQueue<AggStack<T, M>, AggStack<T, Dual<M>>>;
+
+impl<S, F> TwoStackQueue<S, F>
+where
+    S: Stack,
+    F: Stack<Item = S::Item>,
+{
+    /// Creates a new empty queue.
+    pub fn new() -> Self
+    where
+        S: Default,
+        F: Default,
+    {
+        Self::default()
+    }
+
+    /// Creates a new queue from the front and back stacks.
+    ///
+    /// The top of the front stack is the front of the queue, and the top of the back stack
+    /// is the back of the queue.
+    pub fn from_parts(front: F, back: S) -> Self {
+        Self { front, back }
+    }
+
+    /// Returns the front and back stacks.
+    pub fn into_parts(self) -> (F, S) {
+        (self.front, self.back)
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn parts(&self) -> (&F, &S) {
+        (&self.front, &self.back)
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.s_len() + self.back.s_len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.front.s_is_empty() && self.back.s_is_empty()
+    }
+
+    /// Pushes an item to the back of the queue.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the back stack is full.
+    #[inline]
+    pub fn push_back(&mut self, item: S::Item) {
+        self.back.s_push(item);
+    }
+
+    /// Pops the item from the front of the queue or returns `None` if the queue is empty.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the items of the back stack don't fit into the front stack.
+    pub fn pop_front(&mut self) -> Option<S::Item> {
+        if self.front.s_is_empty() {
+            self.front.s_extend(self.back.pop_iter());
+        }
+        self.front.s_pop()
+    }
+
+    /// Returns the shared reference to the item at the front of the queue
+    /// or `None` if the queue is empty.
+    pub fn front(&self) -> Option<&S::Item> {
+        self.front.lifo_ref().or_else(|| {
+            let len = self.back.s_len();
+            self.back.peek_nth(len.checked_sub(1)?)
+        })
+    }
+
+    /// Returns the shared reference to the item at the back of the queue
+    /// or `None` if the queue is empty.
+    pub fn back(&self) -> Option<&S::Item> {
+        self.back.lifo_ref().or_else(|| {
+            let len = self.front.s_len();
+            self.front.peek_nth(len.checked_sub(1)?)
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T, M: Monoid<T>> TwoStackQueue<AggStack<T, M>, AggStack<T, Dual<M>>>
+where
+    M::Agg: Clone,
+{
+    /// Returns the aggregate of the items, from the front to the back of the queue,
+    /// or the identity element if the queue is empty.
+    pub fn aggregate(&self) -> M::Agg {
+        M::combine(&self.front.aggregate(), &self.back.aggregate())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArrayStack;
+
+    #[test]
+    fn fifo_order() {
+        let mut queue = TwoStackQueue::<ArrayStack<u8, 3>>::new();
+        assert_eq!((queue.front(), queue.back()), (None, None));
+        queue.push_back(1);
+        queue.push_back(2);
+        assert_eq!((queue.front(), queue.back()), (Some(&1), Some(&2)));
+        assert_eq!(queue.pop_front(), Some(1));
+        queue.push_back(3);
+        queue.push_back(4);
+        assert_eq!((queue.front(), queue.back()), (Some(&2), Some(&4)));
+        let mut popped = [0; 3];
+        popped.fill_with(|| queue.pop_front().unwrap());
+        assert_eq!(popped, [2, 3, 4]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop_front(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ordered_aggregate() {
+        use alloc::string::String;
+
+        struct Concat;
+
+        impl Monoid<String> for Concat {
+            type Agg = String;
+
+            fn identity() -> String {
+                String::new()
+            }
+
+            fn lift(item: &String) -> String {
+                item.clone()
+            }
+
+            fn combine(a: &String, b: &String) -> String {
+                a.clone() + b
+            }
+        }
+
+        let mut queue = AggQueue::<String, Concat>::new();
+        for word in ["a", "b", "c"] {
+            queue.push_back(String::from(word));
+        }
+        assert_eq!(queue.aggregate(), "abc");
+        assert_eq!(queue.pop_front().as_deref(), Some("a"));
+        queue.push_back(String::from("d"));
+        assert_eq!(queue.aggregate(), "bcd");
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{Stack, StackOpError};
+
+/// An extension trait with the classic [Forth] stack-shuffling words.
+///
+/// It is implemented for every [`Stack`]. In the stack effect comments, the rightmost
+/// item is the top of the stack.
+///
+/// All operations check that the stack is deep enough before touching it,
+/// so on error the stack is left unchanged.
+///
+/// [Forth]: https://forth-standard.org/standard/core
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{ArrayStack, Stack, StackOpError, StackOps};
+///
+/// let mut stack: ArrayStack<i32, 4> = ArrayStack::new();
+/// (1..=3).for_each(|item| stack.s_push(item));
+/// stack.s_rot().unwrap();
+/// assert_eq!(stack.as_slice(), &[2, 3, 1]);
+/// stack.s_over().unwrap();
+/// assert_eq!(stack.as_slice(), &[2, 3, 1, 3]);
+/// assert_eq!(
+///     stack.s_roll(4),
+///     Err(StackOpError::Underflow { required: 5, available: 4 }),
+/// );
+/// ```
+pub trait StackOps: Stack {
+    /// Duplicates the top item: `( a -- a a )`.
+    #[inline]
+    fn s_dup(&mut self) -> Result<(), StackOpError>
+    where
+        Self::Item: Clone,
+    {
+        self.s_pick(0)
+    }
+
+    /// Swaps the two top items: `( a b -- b a )`.
+    #[inline]
+    fn s_swap(&mut self) -> Result<(), StackOpError> {
+        self.s_roll(1)
+    }
+
+    /// Copies the second item to the top: `( a b -- a b a )`.
+    #[inline]
+    fn s_over(&mut self) -> Result<(), StackOpError>
+    where
+        Self::Item: Clone,
+    {
+        self.s_pick(1)
+    }
+
+    /// Rotates the three top items: `( a b c -- b c a )`.
+    #[inline]
+    fn s_rot(&mut self) -> Result<(), StackOpError> {
+        self.s_roll(2)
+    }
+
+    /// Removes the second item: `( a b -- b )`.
+    #[inline]
+    fn s_nip(&mut self) -> Result<(), StackOpError> {
+        require(self, 2)?;
+        // SAFETY: The stack has at least two items, so the call is safe.
+        unsafe { self.s_remove_nth_unchecked(1) };
+        Ok(())
+    }
+
+    /// Copies the top item below the second item: `( a b -- b a b )`.
+    fn s_tuck(&mut self) -> Result<(), StackOpError>
+    where
+        Self::Item: Clone,
+    {
+        require(self, 2)?;
+        // SAFETY: The stack has at least two items, so the call is safe.
+        let b = unsafe { self.lifo_ref_unchecked() }.clone();
+        // The only push that can overflow goes first, so that the stack is unchanged on error.
+        self.s_try_push(b).map_err(|_| StackOpError::Overflow)?;
+        // SAFETY: The stack has at least three items, so the calls are safe.
+        let (b, a) = unsafe { (self.s_pop_unchecked(), self.s_remove_nth_unchecked(1)) };
+        self.s_push(a);
+        self.s_push(b);
+        Ok(())
+    }
+
+    /// Removes the `n` top items: `( a_n ... a_1 -- )`.
+    fn s_drop_n(&mut self, n: usize) -> Result<(), StackOpError> {
+        require(self, n)?;
+        self.s_pop_n(n);
+        Ok(())
+    }
+
+    /// Copies the item at depth `n` to the top: `( a_n ... a_0 -- a_n ... a_0 a_n )`.
+    ///
+    /// `s_pick(0)` is [`StackOps::s_dup`] and `s_pick(1)` is [`StackOps::s_over`].
+    fn s_pick(&mut self, n: usize) -> Result<(), StackOpError>
+    where
+        Self::Item: Clone,
+    {
+        require(self, n.saturating_add(1))?;
+        // SAFETY: The stack is deeper than `n`, so the call is safe.
+        let item = unsafe { self.peek_nth_unchecked(n) }.clone();
+        self.s_try_push(item).map_err(|_| StackOpError::Overflow)
+    }
+
+    /// Moves the item at depth `n` to the top: `( a_n ... a_0 -- a_(n-1) ... a_0 a_n )`.
+    ///
+    /// `s_roll(1)` is [`StackOps::s_swap`] and `s_roll(2)` is [`StackOps::s_rot`].
+    fn s_roll(&mut self, n: usize) -> Result<(), StackOpError> {
+        require(self, n.saturating_add(1))?;
+        // SAFETY: The stack is deeper than `n`, so the call is safe.
+        let item = unsafe { self.s_remove_nth_unchecked(n) };
+        // The removed item freed a slot, so the push can't overflow a bounded stack.
+        self.s_push(item);
+        Ok(())
+    }
+}
+
+impl<S: ?Sized + Stack> StackOps for S {}
+
+fn require<S: ?Sized + Stack>(stack: &S, required: usize) -> Result<(), StackOpError> {
+    let available = stack.s_len();
+    if available < required {
+        Err(StackOpError::Underflow {
+            required,
+            available,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArrayStack, Stack, StackOpError, StackOps};
+
+    #[test]
+    fn shuffling_words() {
+        let mut stack: ArrayStack<i32, 4> = ArrayStack::new();
+        stack.s_push(1);
+        assert_eq!(
+            stack.s_swap(),
+            Err(StackOpError::Underflow {
+                required: 2,
+                available: 1
+            })
+        );
+        stack.s_push(2);
+        stack.s_tuck().unwrap();
+        assert_eq!(stack.as_slice(), &[2, 1, 2]);
+        stack.s_nip().unwrap();
+        assert_eq!(stack.as_slice(), &[2, 2]);
+        stack.s_dup().unwrap();
+        stack.s_over().unwrap();
+        assert_eq!(stack.s_dup(), Err(StackOpError::Overflow));
+        assert_eq!(stack.s_tuck(), Err(StackOpError::Overflow));
+        assert_eq!(stack.as_slice(), &[2, 2, 2, 2]);
+        stack.s_drop_n(4).unwrap();
+        assert!(stack.s_is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use core::mem::ManuallyDrop;
+
+use crate::{LIFOEntry, Stack};
+
+/// An "entry" object corresponding to a tentatively pushed element.
+///
+/// This is constructed with [`Stack::lifo_push_tentative`]. Unless [committed], the element
+/// is popped when this object is dropped, including on panic or on early `?` return.
+///
+/// [committed]: TentativeEntry::commit
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{ArrayStack, Stack};
+///
+/// fn push_parsed(stack: &mut ArrayStack<i32, 4>, digits: &[&str]) -> Option<()> {
+///     let mut entry = stack.lifo_push_tentative(0);
+///     for digit in digits {
+///         *entry = *entry * 10 + digit.parse::<i32>().ok()?;
+///     }
+///     entry.commit();
+///     Some(())
+/// }
+///
+/// let mut stack: ArrayStack<i32, 4> = ArrayStack::new();
+/// assert_eq!(push_parsed(&mut stack, &["4", "2"]), Some(()));
+/// assert_eq!(push_parsed(&mut stack, &["1", "x"]), None);
+/// assert_eq!(stack.as_slice(), &[42]);
+/// ```
+pub struct TentativeEntry<'a, C: ?Sized + Stack>(ManuallyDrop<LIFOEntry<'a, C>>);
+
+impl<'a, C: ?Sized + Stack> TentativeEntry<'a, C> {
+    /// Creates a new "tentative entry" object from the "entry" object of the pushed element.
+    pub fn new(entry: LIFOEntry<'a, C>) -> Self {
+        Self(ManuallyDrop::new(entry))
+    }
+
+    /// Keeps the element on the stack and returns the "entry" object corresponding to it.
+    pub fn commit(self) -> LIFOEntry<'a, C> {
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: The entry is taken exactly once, because the destructor of `this` doesn't run.
+        unsafe { ManuallyDrop::take(&mut this.0) }
+    }
+
+    /// Pops the element from the stack and returns it.
+    pub fn discard(self) -> C::Item {
+        self.commit().pop_pointee()
+    }
+}
+
+impl<'a, C: ?Sized + Stack> core::ops::Deref for TentativeEntry<'a, C> {
+    type Target = C::Item;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, C: ?Sized + Stack> core::ops::DerefMut for TentativeEntry<'a, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<C: ?Sized + Stack> Drop for TentativeEntry<'_, C> {
+    fn drop(&mut self) {
+        // SAFETY: The entry is taken exactly once, because this is the destructor.
+        unsafe { ManuallyDrop::take(&mut self.0) }.pop_pointee();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArrayStack, Stack};
+
+    #[test]
+    fn popped_on_panic() {
+        let mut stack: ArrayStack<i32, 4> = ArrayStack::new();
+        stack.s_push(1);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut entry = stack.lifo_push_tentative(2);
+            *entry += 1;
+            panic!("parsing failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(stack.as_slice(), &[1]);
+
+        let entry = stack.lifo_push_tentative(3);
+        assert_eq!(*entry.commit(), 3);
+        assert_eq!(stack.as_slice(), &[1, 3]);
+    }
+}
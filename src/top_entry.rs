@@ -0,0 +1,101 @@
+use crate::{LIFOEntry, Stack};
+
+/// A view into the top of the stack, which may either be occupied or vacant.
+///
+/// This is constructed with [`Stack::top_entry`] and mirrors
+/// [`hash_map::Entry`] from the standard library.
+///
+/// [`hash_map::Entry`]: https://doc.rust-lang.org/std/collections/hash_map/enum.Entry.html
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{ArrayStack, Stack};
+///
+/// let mut stack: ArrayStack<i32, 4> = ArrayStack::new();
+/// *stack.top_entry().or_default() += 1;
+/// *stack.top_entry().and_modify(|top| *top *= 10).or_push(5) += 1;
+/// assert_eq!(stack.as_slice(), &[11]);
+/// ```
+pub enum TopEntry<'a, C: ?Sized + Stack> {
+    /// The stack is not empty.
+    Occupied(LIFOEntry<'a, C>),
+    /// The stack is empty.
+    Vacant(VacantTop<'a, C>),
+}
+
+/// A view into the top of an empty stack. It is a part of the [`TopEntry`] enum.
+pub struct VacantTop<'a, C: ?Sized>(&'a mut C);
+
+impl<'a, C: ?Sized + Stack> TopEntry<'a, C> {
+    /// Creates a new "top entry" object from the mutable reference to the container.
+    pub fn new(stack: &'a mut C) -> Self {
+        if stack.s_is_empty() {
+            TopEntry::Vacant(VacantTop(stack))
+        } else {
+            // SAFETY: The stack is not empty, so the call is safe.
+            TopEntry::Occupied(unsafe { LIFOEntry::new(stack) })
+        }
+    }
+
+    /// Ensures the stack is not empty by pushing the default value if it is empty
+    /// and returns the "entry" object corresponding to the top element.
+    pub fn or_push(self, default: C::Item) -> LIFOEntry<'a, C> {
+        match self {
+            TopEntry::Occupied(entry) => entry,
+            TopEntry::Vacant(vacant) => vacant.push(default),
+        }
+    }
+
+    /// Ensures the stack is not empty by pushing the result of the `default` function
+    /// if it is empty and returns the "entry" object corresponding to the top element.
+    pub fn or_push_with<F: FnOnce() -> C::Item>(self, default: F) -> LIFOEntry<'a, C> {
+        match self {
+            TopEntry::Occupied(entry) => entry,
+            TopEntry::Vacant(vacant) => vacant.push(default()),
+        }
+    }
+
+    /// Ensures the stack is not empty by pushing [`Default::default`] if it is empty
+    /// and returns the "entry" object corresponding to the top element.
+    pub fn or_default(self) -> LIFOEntry<'a, C>
+    where
+        C::Item: Default,
+    {
+        self.or_push_with(Default::default)
+    }
+
+    /// Provides in-place mutable access to the top element before any potential pushes.
+    pub fn and_modify<F: FnOnce(&mut C::Item)>(self, f: F) -> Self {
+        match self {
+            TopEntry::Occupied(mut entry) => {
+                f(&mut entry);
+                TopEntry::Occupied(entry)
+            }
+            TopEntry::Vacant(vacant) => TopEntry::Vacant(vacant),
+        }
+    }
+
+    /// Converts the "top entry" object into the mutable reference to the stack.
+    pub fn into_stack(self) -> &'a mut C {
+        match self {
+            TopEntry::Occupied(entry) => entry.into_stack(),
+            TopEntry::Vacant(vacant) => vacant.into_stack(),
+        }
+    }
+}
+
+impl<'a, C: ?Sized + Stack> VacantTop<'a, C> {
+    /// Pushes an item to the empty stack and returns the "entry" object
+    /// corresponding to the pushed element.
+    pub fn push(self, item: C::Item) -> LIFOEntry<'a, C> {
+        let VacantTop(stack) = self;
+        stack.lifo_push(item)
+    }
+
+    /// Converts the "vacant top" object into the mutable reference to the stack.
+    pub fn into_stack(self) -> &'a mut C {
+        let VacantTop(stack) = self;
+        stack
+    }
+}
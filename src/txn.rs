@@ -0,0 +1,305 @@
+use alloc::vec::Vec;
+
+use crate::{LIFOEntry, Stack};
+
+/// A record that restores an item below the checkpoint on rollback.
+enum Undo<T> {
+    /// The item was removed from the given index, counted from the bottom.
+    Insert(usize, T),
+    /// The item at the given index, counted from the bottom, was borrowed mutably.
+    Overwrite(usize, T),
+}
+
+/// A transactional checkpoint of the stack that rolls back the changes made since
+/// its creation unless [committed].
+///
+/// This is constructed with [`Stack::checkpoint`]. It implements [`Stack`] itself,
+/// so the changes must be made through it, and checkpoints can be nested.
+///
+/// On rollback, the items pushed since the checkpoint are popped and the items popped or
+/// removed from below the checkpoint are restored to their positions. Items below
+/// the checkpoint are cloned before they are popped, removed or borrowed mutably,
+/// so that in-place modifications are undone as well.
+///
+/// [committed]: StackTxn::commit
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::Stack;
+///
+/// let mut stack = vec![1, 2];
+/// {
+///     let mut txn = stack.checkpoint();
+///     txn.s_pop();
+///     txn.s_push(3);
+///     *txn.lifo_push(4) += 1;
+///     assert_eq!(*txn, vec![1, 3, 5]);
+///     // The transaction is rolled back on drop.
+/// }
+/// assert_eq!(stack, vec![1, 2]);
+/// let mut txn = stack.checkpoint();
+/// txn.s_push(3);
+/// txn.commit();
+/// assert_eq!(stack, vec![1, 2, 3]);
+/// ```
+pub struct StackTxn<'a, C: ?Sized + Stack> {
+    stack: &'a mut C,
+    // The number of items in the stack when the checkpoint was created.
+    base: usize,
+    // The number of items from below the checkpoint that are still in the stack.
+    // They always occupy the bottom of the stack, because new items are pushed above them.
+    kept: usize,
+    undo: Vec<Undo<C::Item>>,
+    // Whether the item at the index, counted from the bottom, was cloned since the last
+    // removal from below the checkpoint. A removal shifts the indices, so it resets the flags.
+    snapshotted: Vec<bool>,
+    committed: bool,
+}
+
+impl<'a, C: ?Sized + Stack> StackTxn<'a, C> {
+    /// Creates a new checkpoint of the stack.
+    pub fn new(stack: &'a mut C) -> Self {
+        let base = stack.s_len();
+        Self {
+            stack,
+            base,
+            kept: base,
+            undo: Vec::new(),
+            snapshotted: Vec::new(),
+            committed: false,
+        }
+    }
+
+    /// Returns the number of items the stack had when the checkpoint was created.
+    pub fn checkpoint_len(&self) -> usize {
+        self.base
+    }
+
+    /// Keeps the changes made since the checkpoint.
+    ///
+    /// If this checkpoint is nested in another one, the changes still can be rolled back
+    /// by the outer checkpoint.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    /// Rolls back the changes made since the checkpoint.
+    ///
+    /// This is equivalent to dropping the checkpoint.
+    pub fn rollback(self) {}
+
+    /// Returns the index from the bottom if the item at depth `n` is below the checkpoint.
+    fn kept_index(&self, n: usize) -> Option<usize> {
+        crate::index_from_depth(self.stack.s_len(), n).filter(|&index| index < self.kept)
+    }
+
+    fn rollback_in_place(&mut self) {
+        self.stack.s_truncate(self.kept);
+        while let Some(undo) = self.undo.pop() {
+            match undo {
+                Undo::Insert(index, item) => {
+                    let above: Vec<C::Item> = self.stack.s_drain_top(self.kept - index).collect();
+                    self.stack.s_push(item);
+                    self.stack.s_extend(above.into_iter().rev());
+                    self.kept += 1;
+                }
+                Undo::Overwrite(index, item) => {
+                    // SAFETY: The item at the index was kept, so the stack is deeper than the depth.
+                    *unsafe { self.stack.peek_nth_mut_unchecked(self.kept - 1 - index) } = item;
+                }
+            }
+        }
+    }
+}
+
+impl<C: ?Sized + Stack> StackTxn<'_, C>
+where
+    C::Item: Clone,
+{
+    /// Clones the item at depth `n` if it is below the checkpoint, so that
+    /// in-place modifications can be undone.
+    fn snapshot(&mut self, n: usize) {
+        let Some(index) = self.kept_index(n) else {
+            return;
+        };
+        // The earlier snapshot already restores the original value.
+        if self.snapshotted.get(index) == Some(&true) {
+            return;
+        }
+        // SAFETY: The index was derived from the depth, so the stack is deeper than `n`.
+        let item = unsafe { self.stack.peek_nth_unchecked(n) }.clone();
+        self.undo.push(Undo::Overwrite(index, item));
+        if self.snapshotted.len() < self.kept {
+            self.snapshotted.resize(self.kept, false);
+        }
+        self.snapshotted[index] = true;
+    }
+
+    /// Clones all items below the checkpoint, so that in-place modifications can be undone.
+    fn snapshot_all(&mut self) {
+        let len = self.stack.s_len();
+        for n in len - self.kept..len {
+            self.snapshot(n);
+        }
+    }
+}
+
+impl<C: ?Sized + Stack> Drop for StackTxn<'_, C> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.rollback_in_place();
+        }
+    }
+}
+
+impl<C: ?Sized + Stack> core::ops::Deref for StackTxn<'_, C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        self.stack
+    }
+}
+
+impl<C: ?Sized + Stack> Stack for StackTxn<'_, C>
+where
+    C::Item: Clone,
+{
+    type Item = C::Item;
+
+    type IterLifo<'b>
+        = C::IterLifo<'b>
+    where
+        Self: 'b;
+    type IterLifoMut<'b>
+        = C::IterLifoMut<'b>
+    where
+        Self: 'b;
+
+    #[inline]
+    fn s_is_empty(&self) -> bool {
+        self.stack.s_is_empty()
+    }
+
+    #[inline]
+    fn s_len(&self) -> usize {
+        self.stack.s_len()
+    }
+
+    #[inline]
+    fn s_push(&mut self, item: Self::Item) {
+        self.stack.s_push(item);
+    }
+
+    #[inline]
+    fn s_try_push(&mut self, item: Self::Item) -> Result<(), crate::CapacityError<Self::Item>> {
+        self.stack.s_try_push(item)
+    }
+
+    #[inline]
+    fn lifo_push(&mut self, item: Self::Item) -> LIFOEntry<'_, Self> {
+        self.stack.s_push(item);
+        // We just pushed to the stack, so the stack is not empty.
+        unsafe { self.lifo_unchecked() }
+    }
+
+    #[inline]
+    fn s_pop(&mut self) -> Option<Self::Item> {
+        self.s_remove_nth(0)
+    }
+
+    #[inline]
+    fn lifo_ref(&self) -> Option<&Self::Item> {
+        self.stack.lifo_ref()
+    }
+
+    #[inline]
+    fn lifo_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peek_nth_mut(0)
+    }
+
+    #[inline]
+    fn iter_lifo(&self) -> Self::IterLifo<'_> {
+        self.stack.iter_lifo()
+    }
+
+    #[inline]
+    fn iter_lifo_mut(&mut self) -> Self::IterLifoMut<'_> {
+        self.snapshot_all();
+        self.stack.iter_lifo_mut()
+    }
+
+    #[inline]
+    fn peek_nth(&self, n: usize) -> Option<&Self::Item> {
+        self.stack.peek_nth(n)
+    }
+
+    #[inline]
+    fn peek_nth_mut(&mut self, n: usize) -> Option<&mut Self::Item> {
+        self.snapshot(n);
+        self.stack.peek_nth_mut(n)
+    }
+
+    fn s_remove_nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = self.kept_index(n);
+        let item = self.stack.s_remove_nth(n)?;
+        if let Some(index) = index {
+            self.undo.push(Undo::Insert(index, item.clone()));
+            self.kept -= 1;
+            self.snapshotted.clear();
+        }
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{Stack, StackOps};
+
+    #[test]
+    fn nested_checkpoints() {
+        let mut stack: Vec<i32> = vec![1, 2, 3, 4];
+        let mut outer = stack.checkpoint();
+        outer.s_push(5);
+        {
+            let mut inner = outer.checkpoint();
+            inner.s_pop();
+            inner.s_remove_nth(1);
+            *inner.peek_nth_mut(0).unwrap() = 40;
+            inner.s_rot().unwrap();
+            inner.s_extend([6, 7]);
+            assert_eq!(**inner, vec![2, 40, 1, 6, 7]);
+            inner.iter_lifo_mut().for_each(|item| *item = 0);
+        }
+        assert_eq!(*outer, vec![1, 2, 3, 4, 5]);
+        {
+            let mut inner = outer.checkpoint();
+            inner.s_truncate(1);
+            inner.s_push(8);
+            inner.commit();
+        }
+        assert_eq!(*outer, vec![1, 8]);
+        outer.rollback();
+        assert_eq!(stack, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn items_cloned_once() {
+        let mut stack: Vec<i32> = vec![1, 2, 3];
+        let mut txn = stack.checkpoint();
+        txn.s_push(4);
+        for _ in 0..10 {
+            txn.iter_lifo_mut().for_each(|item| *item += 1);
+            *txn.peek_nth_mut(1).unwrap() += 1;
+            *txn.peek_nth_mut(2).unwrap() += 1;
+        }
+        assert_eq!(txn.undo.len(), 3);
+        txn.s_remove_nth(3);
+        *txn.peek_nth_mut(1).unwrap() = 0;
+        assert_eq!(txn.undo.len(), 5);
+        txn.rollback();
+        assert_eq!(stack, vec![1, 2, 3]);
+    }
+}
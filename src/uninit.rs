@@ -0,0 +1,109 @@
+use core::mem::MaybeUninit;
+
+use crate::{LIFOEntry, Stack};
+
+/// Implementors of this trait are stacks that store the items contiguously and can expose
+/// the uninitialized slot right above the top element.
+///
+/// This allows constructing large items directly in the storage of the stack
+/// with [`ContiguousStack::lifo_push_uninit`].
+///
+/// ## Safety
+///
+/// Until the stack is mutated in any other way, [`ContiguousStack::spare_top`] must return
+/// the same slot, and after [`ContiguousStack::assume_pushed`] the value written to it must
+/// become the top element of the stack.
+pub unsafe trait ContiguousStack: Stack {
+    /// Returns the uninitialized slot right above the top element, reserving it if needed,
+    /// or `None` if the stack is full.
+    fn spare_top(&mut self) -> Option<&mut MaybeUninit<Self::Item>>;
+
+    /// Makes the slot returned by [`ContiguousStack::spare_top`] the top element of the stack.
+    ///
+    /// ## Safety
+    ///
+    /// The slot must have been returned by [`ContiguousStack::spare_top`]
+    /// and initialized since then.
+    unsafe fn assume_pushed(&mut self);
+
+    /// Reserves the slot right above the top element and returns the "uninit entry" object
+    /// corresponding to it.
+    ///
+    /// ## Panics
+    ///
+    /// Fixed-capacity stacks panic if the stack is full.
+    ///
+    /// ## Also see
+    ///
+    /// * [`ContiguousStack::try_lifo_push_uninit`]
+    #[inline]
+    fn lifo_push_uninit(&mut self) -> UninitEntry<'_, Self> {
+        match self.try_lifo_push_uninit() {
+            Some(entry) => entry,
+            None => panic!("the stack is full"),
+        }
+    }
+
+    /// Reserves the slot right above the top element and returns the "uninit entry" object
+    /// corresponding to it or `None` if the stack is full.
+    ///
+    /// ## Also see
+    ///
+    /// * [`ContiguousStack::lifo_push_uninit`]
+    #[inline]
+    fn try_lifo_push_uninit(&mut self) -> Option<UninitEntry<'_, Self>> {
+        self.spare_top()?;
+        Some(UninitEntry(self))
+    }
+}
+
+/// An "entry" object corresponding to the uninitialized slot right above the top element
+/// of the stack.
+///
+/// This is constructed with [`ContiguousStack::lifo_push_uninit`]. If it is dropped without
+/// being written to, the stack is left unchanged.
+///
+/// ## Example
+///
+/// ```
+/// use stack_trait::{ArrayStack, ContiguousStack};
+///
+/// let mut stack: ArrayStack<[u8; 4096], 2> = ArrayStack::new();
+/// let mut entry = stack.lifo_push_uninit();
+/// // The frame is constructed directly in the storage of the stack.
+/// let frame = entry.as_uninit().as_mut_ptr();
+/// unsafe { frame.cast::<u8>().write_bytes(7, 4096) };
+/// let entry = unsafe { entry.assume_init() };
+/// assert!(entry.iter().all(|&byte| byte == 7));
+/// ```
+pub struct UninitEntry<'a, C: ?Sized>(&'a mut C);
+
+impl<'a, C: ?Sized + ContiguousStack> UninitEntry<'a, C> {
+    /// Returns the mutable reference to the uninitialized slot.
+    pub fn as_uninit(&mut self) -> &mut MaybeUninit<C::Item> {
+        let UninitEntry(stack) = self;
+        // SAFETY: The slot was reserved when the UninitEntry object was created.
+        unsafe { stack.spare_top().unwrap_unchecked() }
+    }
+
+    /// Writes the value to the slot and returns the "entry" object corresponding to the
+    /// pushed element.
+    pub fn write(mut self, value: C::Item) -> LIFOEntry<'a, C> {
+        self.as_uninit().write(value);
+        // SAFETY: The slot was just initialized.
+        unsafe { self.assume_init() }
+    }
+
+    /// Pushes the initialized slot to the stack and returns the "entry" object corresponding
+    /// to the pushed element.
+    ///
+    /// ## Safety
+    ///
+    /// The slot must have been initialized through [`UninitEntry::as_uninit`].
+    pub unsafe fn assume_init(self) -> LIFOEntry<'a, C> {
+        let UninitEntry(stack) = self;
+        stack.assume_pushed();
+        // SAFETY: The element was just pushed, so the stack is not empty.
+        LIFOEntry::new(stack)
+    }
+}